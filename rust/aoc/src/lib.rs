@@ -35,38 +35,269 @@ where
     fn part2(&mut self) -> Anyhow<impl fmt::Display>;
 }
 
-pub mod __runner {
-    pub use aoc_core::auto_input;
-    pub use pastey::paste;
-
-    pub fn format_time(time: std::time::Duration) -> super::Anyhow<String> {
-        let s = format!("{time:#?}");
-
-        let number = s
-            .chars()
-            .take_while(|c| c.is_ascii_digit() || c == &'.')
-            .collect::<String>()
-            .parse::<f64>()?;
-
-        let shift = 10_f64.powi(4 - number.abs().log10().ceil() as i32);
-        let rounded = (number * shift).round() / shift;
-        let unit = s.chars().filter(|c| c.is_alphabetic()).collect::<String>();
-        Ok(format!("{rounded} {unit}"))
+/// A solution registered by `solution!`, which can be run in process by anything linking it.
+#[derive(Clone, Copy)]
+pub struct Registration {
+    /// Source path of the solution, which the year and day are taken from
+    pub path: &'static str,
+    /// Parts the solution implements
+    pub parts: &'static [u8],
+    pub solve: fn(&'static str, &__runner::Options, &str, &[u8]) -> Anyhow<__runner::Report>,
+}
+
+impl Registration {
+    /// Year and day of the puzzle solved.
+    pub fn puzzle(&self) -> Anyhow<(u16, u8)> {
+        __runner::puzzle_from_path(self.path)
     }
 
-    pub fn generalise_durations(durations: &[std::time::Duration]) -> std::time::Duration {
-        let partition = &durations[durations.len() / 4..];
+    /// Loads the input and runs the given parts that the solution implements.
+    pub fn run(&self, options: &__runner::Options, parts: &[u8]) -> Anyhow<__runner::Report> {
+        let input = options.input(self.path)?;
 
-        partition
+        let parts = parts
             .iter()
-            .sum::<std::time::Duration>()
-            .div_f64(partition.len() as f64)
+            .copied()
+            .filter(|part| self.parts.contains(part))
+            .collect::<Vec<_>>();
+
+        (self.solve)(self.path, options, &input, &parts)
+    }
+}
+
+pub mod __runner {
+    pub use aoc_core::{
+        DayReport, PartReport, Report, Timing, auto_input, format_time, head_commit,
+        puzzle_from_path, read_input_file, record_history,
+    };
+
+    use super::{Anyhow, Colorize, Registration, Solver, anyhow};
+
+    /// Checks the command line for any of the given flags.
+    pub fn has_flag(names: &[&str]) -> bool {
+        std::env::args().any(|arg| names.contains(&arg.as_str()))
+    }
+
+    /// Finds the value of a command line option given as `--name value` or `--name=value`.
+    pub fn arg_value(name: &str) -> Option<String> {
+        let args = std::env::args().collect::<Vec<_>>();
+
+        args.iter().enumerate().find_map(|(i, arg)| {
+            if arg == name {
+                args.get(i + 1).cloned()
+            } else {
+                arg.strip_prefix(name)?.strip_prefix('=').map(String::from)
+            }
+        })
+    }
+
+    /// Output and sampling options parsed from the command line.
+    pub struct Options {
+        pub json: bool,
+        pub once: bool,
+        /// Whether to record runs in the benchmark history, only done for release builds
+        pub history: bool,
+        /// Commit recorded in the benchmark history, resolved once for every solution run
+        pub commit: Option<String>,
+        pub input_file: Option<String>,
+        pub run_time: std::time::Duration,
+        pub max_samples: usize,
+        pub warmup: usize,
+    }
+
+    impl Options {
+        pub fn from_args() -> Anyhow<Self> {
+            let json = has_flag(&["--json"])
+                || arg_value("--format").is_some_and(|format| format == "json");
+
+            let once = has_flag(&["--once", "--no-bench"]);
+            let input_file = arg_value("--input-file");
+            let history = !cfg!(debug_assertions)
+                && !once
+                && input_file.is_none()
+                && !has_flag(&["--no-history"]);
+
+            let commit = if history { head_commit() } else { None };
+
+            let run_time = match arg_value("--bench-time") {
+                Some(ms) => std::time::Duration::from_millis(ms.parse()?),
+                None => RUN_TIME,
+            };
+
+            let max_samples = match arg_value("--max-samples") {
+                _ if once => 1,
+                Some(n) => n.parse()?,
+                None => usize::MAX,
+            };
+
+            let warmup = match arg_value("--warmup") {
+                _ if once => 0,
+                Some(n) => n.parse()?,
+                None => WARMUP,
+            };
+
+            Ok(Self {
+                json,
+                once,
+                history,
+                commit,
+                input_file,
+                run_time,
+                max_samples,
+                warmup,
+            })
+        }
+
+        /// Loads the input given by `--input-file`, or the cached input for the solution path.
+        pub fn input(&self, path: &str) -> Anyhow<String> {
+            match &self.input_file {
+                Some(file) => read_input_file(file),
+                None => auto_input(path),
+            }
+        }
+
+        /// Describes a timing as the wall time of a single run, or as sampled statistics.
+        pub fn describe(&self, timing: &Timing) -> Anyhow<String> {
+            if self.once {
+                format_time(timing.duration())
+            } else {
+                timing.summary()
+            }
+        }
     }
 
     pub const RUN_TIME: std::time::Duration = std::time::Duration::from_millis(1000);
+    pub const WARMUP: usize = 5;
+
+    /// Parts selected with `--part1` and `--part2`, in the order they were given.
+    pub fn selected_parts() -> Vec<u8> {
+        std::env::args()
+            .filter_map(|arg| arg.strip_prefix("--part")?.parse().ok())
+            .collect()
+    }
+
+    /// Main function of a solution binary, running the parts selected on the command line.
+    pub fn main(solution: &Registration) -> Anyhow<()> {
+        let options = Options::from_args()?;
+        let report = solution.run(&options, &selected_parts())?;
+
+        if options.json {
+            println!("{}", report.to_json()?);
+        }
+
+        Ok(())
+    }
+
+    fn solve_part<S: Solver>(solution: &mut S, part: u8) -> Anyhow<String> {
+        match part {
+            1 => Ok(solution.part1()?.to_string()),
+            2 => Ok(solution.part2()?.to_string()),
+            _ => Err(anyhow!("invalid part {part}")),
+        }
+    }
+
+    /// Samples parsing and each of the given parts, printing the results unless the output is JSON,
+    /// and records the run in the benchmark history.
+    pub fn run<S: Solver>(
+        path: &'static str,
+        options: &Options,
+        input: &str,
+        parts: &[u8],
+    ) -> Anyhow<Report> {
+        let now = std::time::Instant::now();
+        let mut solution = S::new(input)?;
+        let mut build_durations = vec![now.elapsed()];
+
+        while now.elapsed() < options.run_time && build_durations.len() < options.max_samples {
+            let now = std::time::Instant::now();
+            solution = S::new(input)?;
+            build_durations.push(now.elapsed());
+        }
+
+        let build = Timing::from_samples(&build_durations, options.warmup);
+        let mut total_duration = build.duration();
+
+        let mut report = Report {
+            build,
+            ..Default::default()
+        };
+
+        for &part in parts {
+            let mut temp_sol = solution.clone();
+            let now = std::time::Instant::now();
+            let answer = solve_part(&mut temp_sol, part)?;
+            let mut solution_durations = vec![now.elapsed()];
+
+            while now.elapsed() < options.run_time && solution_durations.len() < options.max_samples
+            {
+                let mut temp_sol = solution.clone();
+                let now = std::time::Instant::now();
+                let next_answer = solve_part(&mut temp_sol, part)?;
+                solution_durations.push(now.elapsed());
+
+                if next_answer != answer {
+                    return Err(anyhow!("inconsistent result"));
+                }
+            }
+
+            let timing = Timing::from_samples(&solution_durations, options.warmup);
+            total_duration += timing.duration();
+
+            if !options.json {
+                println!(
+                    "Part {part} answer: {} {}",
+                    answer.bold().bright_blue(),
+                    format!("({})", options.describe(&timing)?).dimmed(),
+                );
+            }
+
+            report.parts.push(PartReport {
+                part,
+                answer,
+                timing,
+            });
+        }
+
+        report.total_ns = total_duration.as_nanos() as u64;
+
+        // a run is still answered if it cannot be recorded
+        if options.history
+            && let Err(e) = record_history(path, true, options.commit.as_deref(), &report)
+        {
+            eprintln!("Failed to record the run in the benchmark history: {e}");
+        }
+
+        if !options.json {
+            println!(
+                "{}",
+                format!(
+                    "Build: {}  Total: {}",
+                    options.describe(&report.build)?,
+                    format_time(total_duration)?,
+                )
+                .dimmed()
+            );
+        }
+
+        Ok(report)
+    }
 }
 
-/// Generates a main function for the runner to call and includes commonly used imports.
+/// Generates a main function for the runner to call and includes commonly used imports. The
+/// solution is also registered as `SOLUTION`, which the solutions crate links to run every
+/// solution in a single process.
+///
+/// The main function runs the parts selected with `--part1` and `--part2`, and prints the results
+/// as JSON instead of coloured text when given `--json` or `--format json`. Every run of a release
+/// build is appended to the benchmark history in the cache directory unless given `--no-history`.
+///
+/// Each stage is sampled until `--bench-time <ms>` (default 1000) elapses or `--max-samples <n>`
+/// samples are taken, and the first `--warmup <n>` (default 5) samples are discarded. With `--once`
+/// or `--no-bench`, parsing and each part run exactly once and the wall times are printed instead.
+///
+/// The input is the cached download for the year and day of the solution path, unless a file is
+/// given with `--input-file <path>` (or `-` for standard input). Runs with a custom input are not
+/// recorded in the benchmark history.
 ///
 /// Standard library imports:
 /// ```
@@ -108,65 +339,15 @@ macro_rules! solution {
     ($($part:tt),+) => {
         use $crate::*;
 
-        fn main() -> Anyhow<()> {
-            __runner::paste! {
-                let input = __runner::auto_input(file!())?;
-
-                let now = std::time::Instant::now();
-                let mut solution = Solution::new(&input)?;
-                let mut build_durations = vec![now.elapsed()];
-
-                while now.elapsed() < __runner::RUN_TIME {
-                    let now = std::time::Instant::now();
-                    solution = Solution::new(&input)?;
-                    build_durations.push(now.elapsed());
-                }
-
-                let build_duration = __runner::generalise_durations(&build_durations);
-                let mut total_duration = build_duration;
-
-                for arg in std::env::args() {
-                    match arg.as_str() {
-                        $(
-                            concat!("--part", stringify!($part)) => {
-                                let mut temp_sol = solution.clone();
-                                let now = std::time::Instant::now();
-                                let answer = temp_sol.[<part$part>]()?.to_string();
-                                let mut solution_durations = vec![now.elapsed()];
-
-                                while now.elapsed() < __runner::RUN_TIME {
-                                    let mut temp_sol = solution.clone();
-                                    let now = std::time::Instant::now();
-                                    let next_answer = temp_sol.[<part$part>]()?;
-                                    solution_durations.push(now.elapsed());
-
-                                    if next_answer.to_string() != answer {
-                                        return Err(anyhow!("inconsistent result"));
-                                    }
-                                }
-
-                                let solution_duration = __runner::generalise_durations(&solution_durations);
-                                total_duration += solution_duration;
-
-                                println!(
-                                    concat!("Part ", stringify!($part), " answer: {} {}"),
-                                    format!("{}", answer.to_string().bold().bright_blue()),
-                                    format!("({})", __runner::format_time(solution_duration)?).dimmed(),
-                                );
-                            }
-                        )+
-                        _ => {},
-                    }
-                }
-
-                println!("{}",
-                    format!("Build: {}  Total: {}",
-                    __runner::format_time(build_duration)?,
-                    __runner::format_time(total_duration)?,).dimmed()
-                );
+        /// Registration of this solution, for running it in process from the solutions crate.
+        pub const SOLUTION: Registration = Registration {
+            path: file!(),
+            parts: &[$($part),+],
+            solve: __runner::run::<Solution>,
+        };
 
-                Ok(())
-            }
+        fn main() -> Anyhow<()> {
+            __runner::main(&SOLUTION)
         }
     };
 }
@@ -1,16 +1,276 @@
 use std::{
+    collections::BTreeMap,
     env, fs,
     io::Write,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
     process::{self, Stdio},
+    time::{Duration, Instant},
 };
 
-use clap::Parser;
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, error::ErrorKind};
 
-use aoc_core::{AOC_URL, delete_cached_input, load_input};
+use aoc_core::{
+    BASELINE_VAR, Change, DEFAULT_TEMPLATE, DayReport, Error, InputStats, PartReport, Report,
+    Settings, Submission, TemplateValues, add_readme_year, add_workspace_member, baseline_reports,
+    cached_inputs, compare_durations, config, configure, day_header, days_in_year,
+    delete_cached_puzzle, extract_example_answers, extract_title, fill_template, format_time,
+    git_commit, has_example_tests, is_input_cached, is_puzzle_cached, latest_event, load_answers,
+    load_calendar_stars, load_history, load_input, load_leaderboard, load_puzzle_page,
+    load_template, next_unlock, parse_days, puzzle_exists, read_puzzle, readme_entry,
+    readme_titles, record_answer, reload_input, render_leaderboard, render_leaderboard_day,
+    request_wait, save_examples, set_readme_solutions, submit_answer, unlock_time, workspace,
+    year_manifest,
+};
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(
+    version,
+    about,
+    long_about = None,
+    subcommand_negates_reqs = true
+)]
 pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    puzzle: Option<Puzzle>,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+}
+
+impl Cli {
+    /// Parses the command line, rejecting puzzle options along with a subcommand. Only the global
+    /// configuration options can be given on either side of a subcommand.
+    fn try_parse_args<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        let mut command = Self::command();
+        let matches = command.try_get_matches_from_mut(args)?;
+        let cli = Self::from_arg_matches(&matches).map_err(|e| e.format(&mut command))?;
+
+        if cli.command.is_some() && cli.puzzle.is_some() {
+            return Err(command.error(
+                ErrorKind::ArgumentConflict,
+                "puzzle options cannot be used with a subcommand",
+            ));
+        }
+
+        Ok(cli)
+    }
+}
+
+// overrides for the configuration file and environment variables
+#[derive(Args)]
+pub struct ConfigArgs {
+    /// Base URL of the Advent of Code website [env: AOC_URL]
+    #[arg(long, global = true, value_name = "URL")]
+    url: Option<String>,
+
+    /// Directory for cached inputs, puzzle pages and history [env: AOC_CACHE]
+    #[arg(long, global = true, value_name = "PATH")]
+    cache: Option<PathBuf>,
+
+    /// User agent sent with requests [env: AOC_USER_AGENT]
+    #[arg(long, global = true, value_name = "AGENT")]
+    user_agent: Option<String>,
+
+    /// Maximum number of requests within each request period [env: AOC_MAX_REQUESTS]
+    #[arg(long, global = true, value_name = "N")]
+    max_requests: Option<usize>,
+
+    /// Request period in seconds for throttling requests [env: AOC_REQUEST_PERIOD]
+    #[arg(long, global = true, value_name = "SECS")]
+    request_period: Option<u64>,
+
+    /// Only use cached files and fail instead of making requests [env: AOC_OFFLINE]
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Session profile, read from AOC_SESSION_<PROFILE> with its own cache [env: AOC_PROFILE]
+    #[arg(long, global = true, value_name = "NAME")]
+    profile: Option<String>,
+}
+
+impl From<ConfigArgs> for Settings {
+    fn from(args: ConfigArgs) -> Self {
+        Self {
+            url: args.url,
+            cache: args.cache,
+            user_agent: args.user_agent,
+            max_requests: args.max_requests,
+            request_period: args.request_period,
+            offline: args.offline.then_some(true),
+            profile: args.profile,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run every solution in a year and check the answers against the recorded answers
+    Verify {
+        /// Year of the puzzles to verify
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2000..3000))]
+        year: u16,
+
+        /// Run in release mode
+        #[arg(long)]
+        release: bool,
+
+        /// Record answers for parts that have no recorded answer
+        #[arg(long)]
+        record: bool,
+    },
+
+    /// Print the puzzle description, caching the page for offline reading
+    Read {
+        /// Year of the puzzle to read
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2000..3000))]
+        year: u16,
+
+        /// Day of the puzzle to read
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Redownload the puzzle page, e.g. to read part two
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Download every missing input for the unlocked days of a year
+    Fetch {
+        /// Year of the inputs to download
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2000..3000))]
+        year: u16,
+
+        /// Days to download, e.g. `5`, `1..=12` or `1-12` (defaults to every day)
+        #[arg(long, value_parser = parse_days)]
+        days: Option<RangeInclusive<u8>>,
+    },
+
+    /// Show which days of each year have a solution, a cached input, example tests and stars
+    Status {
+        /// Year to show (defaults to every year in the workspace)
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2000..3000))]
+        year: Option<u16>,
+    },
+
+    /// Regenerate the lists of solutions in the README from the solution files and puzzle titles
+    Readme {
+        /// Year to regenerate (defaults to every year in the workspace)
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2000..3000))]
+        year: Option<u16>,
+
+        /// Show the runtime of each solution from its latest release run in the benchmark history
+        #[arg(long)]
+        times: bool,
+    },
+
+    /// Show a private leaderboard, cached for 15 minutes between downloads
+    Leaderboard {
+        /// ID of the private leaderboard, the number at the end of its URL
+        #[arg(long)]
+        id: u64,
+
+        /// Year of the leaderboard (defaults to the latest event)
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2000..3000))]
+        year: Option<u16>,
+
+        /// Show when each member completed a day instead of the overall ranking
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+
+    /// Wait for a puzzle to unlock, then download the input and start it from the template
+    Wait {
+        /// Year of the puzzle to wait for (defaults to the next puzzle to unlock)
+        #[arg(short, long, requires = "day", value_parser = clap::value_parser!(u16).range(2000..3000))]
+        year: Option<u16>,
+
+        /// Day of the puzzle to wait for (defaults to the next puzzle to unlock)
+        #[arg(short, long, requires = "year", value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+
+        /// Open the puzzle page in browser once it unlocks
+        #[arg(short, long)]
+        open: bool,
+
+        /// Template to start the puzzle from, a file name in `aoc_core/templates`
+        #[arg(long, value_name = "NAME", default_value = DEFAULT_TEMPLATE)]
+        template: String,
+    },
+
+    /// Benchmark every solution in a year, built in release mode and run in a single process
+    Bench {
+        /// Year of the puzzles to benchmark
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2000..3000))]
+        year: u16,
+
+        /// Label this run as a named baseline in the benchmark history
+        #[arg(long)]
+        save_baseline: Option<String>,
+
+        /// Compare against the latest run of a named baseline or git reference
+        #[arg(long)]
+        compare: Option<String>,
+
+        /// Percentage change to ignore as noise when comparing
+        #[arg(long, default_value_t = 5.0, requires = "compare")]
+        threshold: f64,
+
+        #[command(flatten)]
+        sampling: Sampling,
+    },
+}
+
+/// Sampling options forwarded to the solution binaries.
+#[derive(Args, Clone)]
+pub struct Sampling {
+    /// Time budget in milliseconds for sampling each stage of a solution
+    #[arg(long, value_name = "MS")]
+    bench_time: Option<u64>,
+
+    /// Maximum number of samples for each stage of a solution
+    #[arg(long, value_name = "N")]
+    max_samples: Option<usize>,
+
+    /// Number of initial samples to discard as warm-up
+    #[arg(long, value_name = "N")]
+    warmup: Option<usize>,
+}
+
+impl Sampling {
+    fn args(&self) -> Vec<String> {
+        [
+            ("--bench-time", self.bench_time.map(|ms| ms.to_string())),
+            ("--max-samples", self.max_samples.map(|n| n.to_string())),
+            ("--warmup", self.warmup.map(|n| n.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some([name.to_string(), value?]))
+        .flatten()
+        .collect()
+    }
+}
+
+/// Delay after a puzzle unlocks before requesting it.
+const UNLOCK_MARGIN: Duration = Duration::from_secs(2);
+
+/// Package linking every solution, used to run a whole year in a single process.
+const SOLUTIONS_PACKAGE: &str = "aoc-solutions";
+
+/// Arguments that make a solution run each stage once, for when only the answers are needed.
+const ANSWER_ONLY: [&str; 1] = ["--once"];
+
+#[derive(Args)]
+pub struct Puzzle {
     /// Year of the puzzle to select
     #[arg(short, long, value_parser = clap::value_parser!(u16).range(2000..3000))]
     year: u16,
@@ -35,14 +295,26 @@ pub struct Cli {
     #[arg(short, long, group = "exec")]
     test: bool,
 
+    /// Run the selected part and submit the answer
+    #[arg(short, long, group = "exec", requires = "part")]
+    submit: bool,
+
     /// Print the selected puzzle input
     #[arg(short, long)]
     input: bool,
 
+    /// Print the shape of the input with suggested types instead of the input itself
+    #[arg(long, requires = "input")]
+    stats: bool,
+
     /// Start new puzzle from template
     #[arg(short, long, group = "exec")]
     new: bool,
 
+    /// Template to start a new puzzle from, a file name in `aoc_core/templates`
+    #[arg(long, value_name = "NAME", requires = "new", default_value = DEFAULT_TEMPLATE)]
+    template: String,
+
     /// Force redownloading the input and overwrite the cached file
     #[arg(long)]
     no_cache: bool,
@@ -50,50 +322,225 @@ pub struct Cli {
     /// Open the selected puzzle page in browser
     #[arg(short, long)]
     open: bool,
+
+    /// Run the selected puzzle with the input from a file, or `-` for standard input
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "exec",
+        conflicts_with = "submit"
+    )]
+    input_file: Option<String>,
+
+    /// Run the selected puzzle once against the cached input of every profile
+    #[arg(
+        long,
+        requires = "exec",
+        conflicts_with_all = ["input_file", "submit", "test"]
+    )]
+    all_profiles: bool,
+
+    /// Run parsing and each part once without benchmarking
+    #[arg(
+        long,
+        alias = "no-bench",
+        requires = "exec",
+        conflicts_with_all = ["bench_time", "max_samples", "warmup"]
+    )]
+    once: bool,
+
+    // sampling options are repeated here because clap cannot detect a nested flatten inside the
+    // optional puzzle arguments
+    /// Time budget in milliseconds for sampling each stage of a solution
+    #[arg(long, value_name = "MS", requires = "exec")]
+    bench_time: Option<u64>,
+
+    /// Maximum number of samples for each stage of a solution
+    #[arg(long, value_name = "N", requires = "exec")]
+    max_samples: Option<usize>,
+
+    /// Number of initial samples to discard as warm-up
+    #[arg(long, value_name = "N", requires = "exec")]
+    warmup: Option<usize>,
+}
+
+impl Puzzle {
+    fn sampling(&self) -> Sampling {
+        Sampling {
+            bench_time: self.bench_time,
+            max_samples: self.max_samples,
+            warmup: self.warmup,
+        }
+    }
+}
+
+/// A solution or its tests exited unsuccessfully, after printing their own errors.
+#[derive(Debug, thiserror::Error)]
+#[error("{year} day {day} {} ({status})", if *.test { "tests failed" } else { "failed" })]
+struct SolutionFailed {
+    year: u16,
+    day: u8,
+    test: bool,
+    status: process::ExitStatus,
+}
+
+fn exit_code(error: &anyhow::Error) -> i32 {
+    match error.downcast_ref::<Error>() {
+        Some(Error::MissingSession { .. }) => 10,
+        Some(Error::Unauthorised { .. }) => 11,
+        Some(Error::Locked { .. } | Error::NotUnlocked { .. }) => 12,
+        Some(Error::Throttled { .. }) => 13,
+        Some(Error::Http { .. } | Error::NotFound { .. }) => 14,
+        Some(Error::Network { .. }) => 15,
+        Some(Error::Cache { .. }) => 16,
+        Some(Error::Config(_)) => 18,
+        Some(Error::InvalidInput { .. }) => 19,
+        Some(Error::NoSuchPuzzle { .. }) => 20,
+        Some(Error::Offline { .. }) => 21,
+        None if error.is::<SolutionFailed>() => 3,
+        None => 1,
+    }
 }
 
 fn main() -> ! {
     match cli() {
         Ok(_) => std::process::exit(0),
         Err(e) => {
-            eprint!("{e}");
-            std::process::exit(1)
+            eprintln!("{e}");
+            std::process::exit(exit_code(&e))
         }
     }
 }
 
 fn cli() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+    let cli = Cli::try_parse_args(env::args_os()).unwrap_or_else(|e| e.exit());
+    configure(cli.config.into())?;
 
+    match cli.command {
+        Some(Command::Verify {
+            year,
+            release,
+            record,
+        }) => verify(year, release, record),
+        Some(Command::Read { year, day, refresh }) => {
+            if refresh {
+                delete_cached_puzzle(year, day)?;
+            }
+
+            println!("{}", read_puzzle(year, day)?);
+            Ok(())
+        }
+        Some(Command::Fetch { year, days }) => fetch(year, days),
+        Some(Command::Status { year }) => status(year),
+        Some(Command::Readme { year, times }) => {
+            let workspace = workspace()?;
+
+            let years = match year {
+                Some(year) => vec![year],
+                None => workspace_years(&workspace)?,
+            };
+
+            update_readme(&workspace, &years, times)?;
+            println!("Updated the solutions in README.md");
+            Ok(())
+        }
+        Some(Command::Leaderboard { id, year, day }) => {
+            let year = year.unwrap_or_else(|| latest_event(Utc::now()));
+            let leaderboard = load_leaderboard(year, id)?;
+
+            match day {
+                Some(day) => println!("{}", render_leaderboard_day(&leaderboard, day)),
+                None => println!("{}", render_leaderboard(&leaderboard, year)),
+            }
+
+            Ok(())
+        }
+        Some(Command::Wait {
+            year,
+            day,
+            open,
+            template,
+        }) => wait(year.zip(day), open, &template),
+        Some(Command::Bench {
+            year,
+            save_baseline,
+            compare,
+            threshold,
+            sampling,
+        }) => bench(year, save_baseline, compare, threshold, &sampling.args()),
+        None => select(
+            cli.puzzle
+                .expect("puzzle arguments are required without a subcommand"),
+        ),
+    }
+}
+
+fn select(cli: Puzzle) -> anyhow::Result<()> {
     if cli.no_cache {
-        delete_cached_input(cli.year, cli.day)?;
+        reload_input(cli.year, cli.day)?;
     }
 
-    if cli.run || (!cli.test && !cli.new && !cli.input && !cli.open) {
-        run(cli.year, cli.day, cli.part, cli.release, false)?;
+    if cli.all_profiles {
+        run_profiles(cli.year, cli.day, cli.part, cli.release)?;
+    } else if cli.run || (!cli.test && !cli.submit && !cli.new && !cli.input && !cli.open) {
+        let mut args = if cli.once {
+            vec!["--once".to_string()]
+        } else {
+            cli.sampling().args()
+        };
+
+        if let Some(path) = &cli.input_file {
+            args.extend(["--input-file".to_string(), path.clone()]);
+        } else {
+            // fetch the input here so download failures are reported with their exit codes
+            load_input(cli.year, cli.day)?;
+        }
+
+        run(cli.year, cli.day, cli.part, cli.release, false, &args)?;
     }
 
     if cli.test {
-        run(cli.year, cli.day, cli.part, cli.release, true)?;
+        run(cli.year, cli.day, cli.part, cli.release, true, &[])?;
+    }
+
+    if cli.submit
+        && let Some(part) = cli.part
+    {
+        submit(cli.year, cli.day, part, cli.release)?;
     }
 
     if cli.new {
-        make_new(cli.year, cli.day)?;
+        make_new(cli.year, cli.day, &cli.template)?;
     }
 
     if cli.input {
-        print!("{}", load_input(cli.year, cli.day)?);
+        let input = load_input(cli.year, cli.day)?;
+
+        if cli.stats {
+            let stats = InputStats::new(&input);
+            print!("{stats}");
+            print_suggestions(&stats);
+        } else {
+            print!("{input}");
+        }
     }
 
     if cli.open {
-        let url = format!("{AOC_URL}/{}/day/{}", cli.year, cli.day);
+        let url = format!("{}/{}/day/{}", config()?.url, cli.year, cli.day);
         webbrowser::open(&url)?;
     }
 
     Ok(())
 }
 
-fn run(year: u16, day: u8, part: Option<u8>, release: bool, test: bool) -> anyhow::Result<()> {
+fn cargo_command(
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+    release: bool,
+    test: bool,
+    extra: &[String],
+) -> anyhow::Result<process::Command> {
     let mut args = vec![
         (if test { "test" } else { "run" }).to_string(),
         "--package".to_string(),
@@ -117,28 +564,520 @@ fn run(year: u16, day: u8, part: Option<u8>, release: bool, test: bool) -> anyho
                 args.push("--part2".to_string());
             }
         }
+
+        args.extend_from_slice(extra);
     }
 
-    let exit_status = process::Command::new("cargo")
+    let mut command = process::Command::new("cargo");
+    command
         .args(&args)
+        .envs(config()?.env())
+        .current_dir(workspace()?);
+    Ok(command)
+}
+
+fn run(
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+    release: bool,
+    test: bool,
+    args: &[String],
+) -> anyhow::Result<()> {
+    let status = cargo_command(year, day, part, release, test, args)?
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()?
         .wait()?;
 
-    if exit_status.success() {
+    if status.success() {
+        Ok(())
+    } else {
+        Err(SolutionFailed {
+            year,
+            day,
+            test,
+            status,
+        }
+        .into())
+    }
+}
+
+/// Runs a puzzle once against the cached input of every profile, checking that each run produced
+/// answers for the selected parts.
+fn run_profiles(year: u16, day: u8, part: Option<u8>, release: bool) -> anyhow::Result<()> {
+    let inputs = cached_inputs(year, day)?;
+
+    if inputs.is_empty() {
+        return Err(anyhow::anyhow!(
+            "no profile has a cached input for {year} day {day}"
+        ));
+    }
+
+    let parts = part.map_or(vec![1, 2], |part| vec![part]);
+    let mut complete = 0;
+
+    for (profile, path) in &inputs {
+        let args = ANSWER_ONLY
+            .iter()
+            .map(|arg| arg.to_string())
+            .chain([
+                "--json".to_string(),
+                "--input-file".to_string(),
+                path.to_string_lossy().to_string(),
+            ])
+            .collect::<Vec<_>>();
+
+        let output = cargo_command(year, day, part, release, false, &args)?
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        let report = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .last()
+            .filter(|_| output.status.success())
+            .and_then(|line| Report::from_json(line).ok());
+
+        let answers = parts
+            .iter()
+            .map(|&part| report.as_ref().and_then(|report| report.answer(part)))
+            .collect::<Vec<_>>();
+
+        let summary = parts
+            .iter()
+            .zip(&answers)
+            .map(|(part, answer)| format!("part {part}: {}", answer.unwrap_or("missing")))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        println!("{:<16} {summary}", profile.as_deref().unwrap_or("default"));
+
+        if answers.iter().all(Option::is_some) {
+            complete += 1;
+        }
+    }
+
+    println!(
+        "Answers produced for {complete} of {} profiles",
+        inputs.len()
+    );
+
+    if complete == inputs.len() {
         Ok(())
     } else {
-        Err(anyhow::anyhow!(""))
+        Err(anyhow::anyhow!(
+            "{} profiles did not produce every answer",
+            inputs.len() - complete
+        ))
+    }
+}
+
+fn solve(year: u16, day: u8, part: u8, release: bool) -> anyhow::Result<Report> {
+    load_input(year, day)?;
+
+    let output = cargo_command(year, day, Some(part), release, false, &[])?
+        .args(ANSWER_ONLY)
+        .arg("--json")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(SolutionFailed {
+            year,
+            day,
+            test: false,
+            status: output.status,
+        }
+        .into());
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+
+    stdout
+        .lines()
+        .last()
+        .ok_or_else(|| anyhow::anyhow!("no output from {year} day {day}"))
+        .and_then(Report::from_json)
+}
+
+fn submit(year: u16, day: u8, part: u8, release: bool) -> anyhow::Result<()> {
+    let report = solve(year, day, part, release)?;
+
+    let answer = report
+        .answer(part)
+        .ok_or_else(|| anyhow::anyhow!("failed to find the answer for part {part}"))?;
+
+    println!("Submitting {answer} for {year} day {day} part {part}...");
+
+    match submit_answer(year, day, part, answer)? {
+        Submission::Correct => {
+            println!("That's the right answer!");
+            record_answer(year, day, part, answer)?;
+            delete_cached_puzzle(year, day)?;
+        }
+        Submission::Incorrect => println!("That's not the right answer."),
+        Submission::TooHigh => println!("That's not the right answer, it is too high."),
+        Submission::TooLow => println!("That's not the right answer, it is too low."),
+        Submission::RateLimited(wait) => {
+            println!("You gave an answer too recently, wait {wait:#?} before trying again.")
+        }
+        Submission::AlreadySolved => println!("This part has already been solved."),
+        Submission::Unknown(text) => {
+            return Err(anyhow::anyhow!(
+                "Unrecognised response to submission:\n{text}"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the solutions crate, which links every solution into a single binary, returning the path
+/// of the binary.
+fn build_solutions(release: bool) -> anyhow::Result<PathBuf> {
+    let mut command = process::Command::new("cargo");
+
+    command.current_dir(workspace()?).args([
+        "build",
+        "--package",
+        SOLUTIONS_PACKAGE,
+        "--bin",
+        SOLUTIONS_PACKAGE,
+        "--message-format=json-render-diagnostics",
+    ]);
+
+    if release {
+        command.arg("--release");
+    }
+
+    let output = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("failed to build {SOLUTIONS_PACKAGE}"));
+    }
+
+    String::from_utf8(output.stdout)?
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find_map(|message| Some(PathBuf::from(message["executable"].as_str()?)))
+        .ok_or_else(|| anyhow::anyhow!("failed to find the {SOLUTIONS_PACKAGE} binary"))
+}
+
+/// Runs every solution of a year in process with the solutions binary, returning the result of
+/// each day that finished before the process exited.
+fn run_solutions(
+    path: &Path,
+    year: u16,
+    baseline: Option<&str>,
+    args: &[&str],
+) -> anyhow::Result<Vec<DayReport>> {
+    let mut command = process::Command::new(path);
+    command.envs(config()?.env());
+
+    if let Some(baseline) = baseline {
+        command.env(BASELINE_VAR, baseline);
+    }
+
+    let output = command
+        .args(["--year", &year.to_string(), "--part1", "--part2", "--json"])
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    // solutions abort on panics, so the days reported before one are kept and the rest are run
+    // with their own binary by the caller
+    if !output.status.success() {
+        eprintln!(
+            "Solutions for {year} exited unsuccessfully ({}), running the remaining days separately",
+            output.status
+        );
+    }
+
+    // solutions may print their own output, so only lines that parse as reports are kept
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| DayReport::from_json(line).ok())
+        .collect())
+}
+
+/// Runs every solution of a year, in process where the solutions binary links them and with their
+/// own binary otherwise, returning the report of each day or `None` if it failed to run. Without a
+/// solutions binary every day runs with its own binary.
+fn run_year(
+    path: Option<&Path>,
+    year: u16,
+    release: bool,
+    baseline: Option<&str>,
+    args: &[&str],
+) -> anyhow::Result<Vec<(u8, Option<Report>)>> {
+    let reports = match path {
+        Some(path) => run_solutions(path, year, baseline, args)?,
+        None => Vec::new(),
+    };
+
+    let mut reports = reports
+        .into_iter()
+        .map(|report| (report.day, report.report))
+        .collect::<BTreeMap<_, _>>();
+
+    let directory = workspace()?.join(format!("{year}/src/bin"));
+
+    let args = args
+        .iter()
+        .map(|arg| arg.to_string())
+        .chain(["--json".to_string()])
+        .collect::<Vec<_>>();
+
+    for day in 1..=days_in_year(year) {
+        if reports.contains_key(&day) || !directory.join(format!("day{day:02}.rs")).is_file() {
+            continue;
+        }
+
+        let mut command = cargo_command(year, day, None, release, false, &args)?;
+
+        if let Some(baseline) = baseline {
+            command.env(BASELINE_VAR, baseline);
+        }
+
+        let output = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        let report = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .last()
+            .filter(|_| output.status.success())
+            .and_then(|line| Report::from_json(line).ok());
+
+        reports.insert(day, report);
+    }
+
+    Ok(reports.into_iter().collect())
+}
+
+fn verify(year: u16, release: bool, record: bool) -> anyhow::Result<()> {
+    let recorded = load_answers()?;
+    let mut mismatches = 0;
+    let mut failures = 0;
+
+    // a solution dependency that fails to build only fails its own day
+    let path = build_solutions(release)
+        .inspect_err(|e| eprintln!("{e}, running each day with its own binary"))
+        .ok();
+
+    for (day, report) in run_year(path.as_deref(), year, release, None, &ANSWER_ONLY)? {
+        let Some(report) = report else {
+            println!("Day {day:02}: failed to run");
+            failures += 1;
+            continue;
+        };
+
+        for PartReport { part, answer, .. } in report.parts {
+            match recorded.get(&(year, day, part)) {
+                Some(expected) if expected == &answer => println!("Day {day:02} part {part}: ok"),
+                Some(expected) => {
+                    println!("Day {day:02} part {part}: expected {expected} but got {answer}");
+                    mismatches += 1;
+                }
+                None if record => {
+                    record_answer(year, day, part, &answer)?;
+                    println!("Day {day:02} part {part}: recorded {answer}");
+                }
+                None => println!("Day {day:02} part {part}: no recorded answer ({answer})"),
+            }
+        }
+    }
+
+    if mismatches > 0 || failures > 0 {
+        Err(anyhow::anyhow!(
+            "{mismatches} answers did not match and {failures} solutions failed to run"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn bench(
+    year: u16,
+    save_baseline: Option<String>,
+    compare: Option<String>,
+    threshold: f64,
+    args: &[String],
+) -> anyhow::Result<()> {
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let now = Instant::now();
+    let path = match build_solutions(true) {
+        Ok(path) => {
+            println!(
+                "Compiled {SOLUTIONS_PACKAGE} in {}\n",
+                format_time(now.elapsed())?
+            );
+            Some(path)
+        }
+        Err(e) => {
+            eprintln!("{e}, running each day with its own binary\n");
+            None
+        }
+    };
+
+    let started = Utc::now();
+    let results = run_year(path.as_deref(), year, true, save_baseline.as_deref(), &args)?;
+
+    println!(
+        "{:>5}  {:>12}  {:>12}  {:>12}  {:>12}",
+        "Day", "Build", "Part 1", "Part 2", "Total"
+    );
+
+    let mut reports = Vec::new();
+    let mut totals = [Duration::ZERO; 4];
+
+    for (day, report) in results {
+        let Some(report) = report else {
+            println!("{day:>5}  {:>12}", "failed");
+            continue;
+        };
+
+        let mut line = format!("{day:>5}");
+
+        for (total, duration) in totals.iter_mut().zip(report.durations()) {
+            *total += duration.unwrap_or_default();
+
+            let cell = match duration {
+                Some(duration) => format_time(duration)?,
+                None => "-".to_string(),
+            };
+
+            line.push_str(&format!("  {cell:>12}"));
+        }
+
+        println!("{line}");
+        reports.push((day, report));
+    }
+
+    let mut line = format!("{:>5}", "Total");
+
+    for total in totals {
+        line.push_str(&format!("  {:>12}", format_time(total)?));
+    }
+
+    println!("{line}");
+
+    if let Some(reference) = compare {
+        compare_history(year, &reference, threshold, started, &reports)?;
+    }
+
+    Ok(())
+}
+
+fn compare_history(
+    year: u16,
+    reference: &str,
+    threshold: f64,
+    started: DateTime<Utc>,
+    reports: &[(u8, Report)],
+) -> anyhow::Result<()> {
+    let commit = git_commit(reference);
+    let previous = baseline_reports(load_history()?, year, reference, commit.as_deref(), started)?;
+
+    println!("\nCompared against {reference} (noise threshold {threshold}%)\n");
+
+    println!(
+        "{:>5}  {:>6}  {:>12}  {:>12}  {:>8}",
+        "Day", "Stage", "Before", "After", "Change"
+    );
+
+    let stages = ["Build", "Part 1", "Part 2", "Total"];
+    let (mut faster, mut slower) = (0, 0);
+
+    for (day, report) in reports {
+        let Some(before) = previous.get(day) else {
+            continue;
+        };
+
+        for ((stage, before), after) in stages
+            .iter()
+            .zip(before.durations())
+            .zip(report.durations())
+        {
+            let (Some(before), Some(after)) = (before, after) else {
+                continue;
+            };
+
+            let (change, verdict) = compare_durations(before, after, threshold);
+
+            let verdict = match verdict {
+                Change::Faster => {
+                    faster += 1;
+                    "faster"
+                }
+                Change::Slower => {
+                    slower += 1;
+                    "slower"
+                }
+                Change::Noise => "",
+            };
+
+            let line = format!(
+                "{day:>5}  {stage:>6}  {:>12}  {:>12}  {:>+7.1}%  {verdict}",
+                format_time(before)?,
+                format_time(after)?,
+                change,
+            );
+
+            println!("{}", line.trim_end());
+        }
     }
+
+    println!("\n{faster} faster and {slower} slower beyond the noise threshold");
+    Ok(())
+}
+
+/// Creates the crate of a year that has no solutions yet, registering it as a workspace member and
+/// adding a section for it to the README.
+fn make_year(workspace: &Path, year: u16) -> anyhow::Result<()> {
+    let manifest = workspace.join(format!("{year}/Cargo.toml"));
+
+    if manifest.is_file() {
+        return Ok(());
+    }
+
+    let directory = workspace.join(format!("{year}/src/bin"));
+    fs::create_dir_all(&directory).with_context(|| format!("failed to create {directory:?}"))?;
+    fs::write(&manifest, year_manifest(year))
+        .with_context(|| format!("failed to write {manifest:?}"))?;
+
+    let path = workspace.join("Cargo.toml");
+    let members = add_workspace_member(&read_file(&path)?, &year.to_string())?;
+    fs::write(&path, members).with_context(|| format!("failed to write {path:?}"))?;
+
+    let path = workspace.join("README.md");
+
+    if path.is_file() {
+        fs::write(&path, add_readme_year(&read_file(&path)?, year))
+            .with_context(|| format!("failed to write {path:?}"))?;
+    }
+
+    println!("Created aoc-{year} and added it to the workspace");
+    Ok(())
 }
 
-fn make_new(year: u16, day: u8) -> anyhow::Result<()> {
-    let cwd = env::current_dir()?;
-    let directory = cwd.join(format!("{year}/src/bin"));
+fn make_new(year: u16, day: u8, template: &str) -> anyhow::Result<()> {
+    let template = load_template(template)?;
+    let workspace = workspace()?;
+    make_year(&workspace, year)?;
+
+    let directory = workspace.join(format!("{year}/src/bin"));
 
     if !directory.is_dir() {
-        fs::create_dir(&directory)?;
+        fs::create_dir_all(&directory)
+            .with_context(|| format!("failed to create {directory:?}"))?;
     }
 
     let path = directory.join(format!("day{day:02}.rs"));
@@ -146,12 +1085,375 @@ fn make_new(year: u16, day: u8) -> anyhow::Result<()> {
     if path.exists() {
         let timestamp = chrono::Local::now().format("%F_%H.%M.%S");
         let backup_path = directory.join(format!("day{day:02}_{timestamp}.rs"));
-        fs::copy(&path, backup_path)?;
+        fs::copy(&path, &backup_path)
+            .with_context(|| format!("failed to back up {path:?} to {backup_path:?}"))?;
+    }
+
+    let mut values = TemplateValues {
+        year,
+        day,
+        ..Default::default()
+    };
+
+    match save_examples(year, day) {
+        Ok(examples) => {
+            for (path, _) in &examples {
+                println!("Saved example to {path:?}");
+            }
+
+            values.example = examples
+                .into_iter()
+                .map(|(_, example)| example)
+                .max_by_key(String::len)
+                .unwrap_or_default();
+
+            // the page was cached when the examples were saved
+            let page = load_puzzle_page(year, day)?;
+            values.title = extract_title(&page).unwrap_or_default();
+            values.answers = extract_example_answers(&page);
+        }
+        Err(e) => eprintln!("Failed to fetch examples: {e}"),
     }
 
-    let bytes = include_bytes!("../template.rs");
-    let mut file = fs::File::create(path)?;
-    file.write_all(bytes)?;
+    fs::write(&path, fill_template(&template, &values))
+        .with_context(|| format!("failed to write {path:?}"))?;
+
+    update_readme(&workspace, &[year], false)?;
+
+    match load_input(year, day) {
+        Ok(input) => print_suggestions(&InputStats::new(&input)),
+        Err(e) => eprintln!("Failed to fetch the input for suggested types: {e}"),
+    }
 
     Ok(())
 }
+
+fn print_suggestions(stats: &InputStats) {
+    let suggestions = stats.suggestions();
+
+    if !suggestions.is_empty() {
+        println!("\nSuggested types from the input:");
+
+        for suggestion in suggestions {
+            println!("    {suggestion}");
+        }
+    }
+}
+
+fn wait(puzzle: Option<(u16, u8)>, open: bool, template: &str) -> anyhow::Result<()> {
+    let (year, day, unlock) = match puzzle {
+        Some((year, day)) => (year, day, unlock_time(year, day)),
+        None => next_unlock(Utc::now()),
+    };
+
+    if !puzzle_exists(year, day) {
+        return Err(Error::NoSuchPuzzle { year, day }.into());
+    }
+
+    if let Ok(remaining) = (unlock - Utc::now()).to_std() {
+        println!(
+            "Waiting for {year} day {day} to unlock at {}",
+            unlock.with_timezone(&chrono::Local).format("%F %T %:z")
+        );
+
+        let mut remaining = remaining;
+
+        while !remaining.is_zero() {
+            let seconds = remaining.as_secs();
+
+            print!(
+                "\r{:02}:{:02}:{:02} remaining",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            );
+
+            std::io::stdout().flush()?;
+            std::thread::sleep(remaining.min(Duration::from_secs(1)));
+            remaining = (unlock - Utc::now()).to_std().unwrap_or_default();
+        }
+
+        println!();
+
+        // allow for the clock differing from the server before requesting the input
+        std::thread::sleep(UNLOCK_MARGIN);
+    }
+
+    load_input(year, day)?;
+    println!("Downloaded the input for {year} day {day}");
+
+    make_new(year, day, template)?;
+
+    if open {
+        webbrowser::open(&format!("{}/{year}/day/{day}", config()?.url))?;
+    }
+
+    Ok(())
+}
+
+/// Years with a crate in the workspace, found as directories named after the year.
+fn workspace_years(workspace: &Path) -> anyhow::Result<Vec<u16>> {
+    let mut years = fs::read_dir(workspace)
+        .with_context(|| format!("failed to read {workspace:?}"))?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let year = entry.file_name().to_str()?.parse::<u16>().ok()?;
+            entry.path().join("src/bin").is_dir().then_some(year)
+        })
+        .collect::<Vec<_>>();
+
+    years.sort_unstable();
+    Ok(years)
+}
+
+/// Rewrites the README lists of solutions for some years. Titles already in the README are kept,
+/// and the others are taken from the puzzle pages, which are cached once downloaded.
+fn update_readme(workspace: &Path, years: &[u16], times: bool) -> anyhow::Result<()> {
+    let path = workspace.join("README.md");
+
+    if !path.is_file() {
+        return Ok(());
+    }
+
+    // history is in chronological order, so later runs overwrite earlier ones
+    let latest = if times {
+        load_history()?
+            .into_iter()
+            .filter(|entry| entry.release)
+            .map(|entry| ((entry.year, entry.day), entry.report))
+            .collect::<BTreeMap<_, _>>()
+    } else {
+        BTreeMap::new()
+    };
+
+    let mut readme = read_file(&path)?;
+    let mut online = !config()?.offline;
+
+    for &year in years {
+        let titles = readme_titles(&readme, year);
+        let mut entries = Vec::new();
+
+        for day in 1..=days_in_year(year) {
+            if !workspace
+                .join(format!("{year}/src/bin/day{day:02}.rs"))
+                .is_file()
+            {
+                continue;
+            }
+
+            let title = match titles.get(&day) {
+                Some(title) => Some(title.clone()),
+                None if online || is_puzzle_cached(year, day)? => load_puzzle_page(year, day)
+                    .inspect_err(|e| {
+                        eprintln!("Failed to fetch titles, listing days without them: {e}");
+                        online = false;
+                    })
+                    .ok()
+                    .and_then(|page| extract_title(&page)),
+                None => None,
+            };
+
+            let time = latest
+                .get(&(year, day))
+                .map(|report| format_time(Duration::from_nanos(report.total_ns)))
+                .transpose()?;
+
+            entries.push(readme_entry(year, day, title.as_deref(), time.as_deref()));
+        }
+
+        readme = set_readme_solutions(&readme, year, &entries);
+    }
+
+    fs::write(&path, readme).with_context(|| format!("failed to write {path:?}"))?;
+    Ok(())
+}
+
+fn read_file(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path).with_context(|| format!("failed to read {path:?}"))
+}
+
+fn status(year: Option<u16>) -> anyhow::Result<()> {
+    let workspace = workspace()?;
+
+    let years = match year {
+        Some(year) => vec![year],
+        None => workspace_years(&workspace)?,
+    };
+
+    let mut online = !config()?.offline;
+
+    for year in years {
+        let days = days_in_year(year);
+        let solution = |day: u8| workspace.join(format!("{year}/src/bin/day{day:02}.rs"));
+
+        let solutions = (1..=days)
+            .map(|day| solution(day).is_file())
+            .collect::<Vec<_>>();
+
+        let inputs = (1..=days)
+            .map(|day| is_input_cached(year, day))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let examples = (1..=days)
+            .map(|day| {
+                fs::read_to_string(solution(day)).is_ok_and(|source| has_example_tests(&source))
+            })
+            .collect::<Vec<_>>();
+
+        let stars = if online && unlock_time(year, 1) <= Utc::now() {
+            load_calendar_stars(year)
+                .inspect_err(|e| {
+                    eprintln!("Failed to fetch stars, showing local status only: {e}");
+                    online = false;
+                })
+                .ok()
+        } else {
+            None
+        };
+
+        let row = |name: &str, present: &[bool]| {
+            let marks = present
+                .iter()
+                .map(|&present| if present { '#' } else { '.' })
+                .collect::<String>();
+
+            let count = present.iter().filter(|&&present| present).count();
+            println!("{name:<10} {marks}  {count}/{days}");
+        };
+
+        let [tens, ones] = day_header(days);
+
+        println!("{year:<10} {tens}");
+        println!("{:<10} {ones}", "");
+        row("Solution", &solutions);
+        row("Input", &inputs);
+        row("Examples", &examples);
+
+        if let Some(stars) = stars {
+            let marks = (1..=days)
+                .map(|day| match stars.get(&day) {
+                    Some(2) => '*',
+                    Some(1) => '+',
+                    _ => '.',
+                })
+                .collect::<String>();
+
+            let count = stars.values().map(|&stars| u32::from(stars)).sum::<u32>();
+            println!("{:<10} {marks}  {count}/{}", "Stars", u32::from(days) * 2);
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
+
+fn fetch(year: u16, days: Option<RangeInclusive<u8>>) -> anyhow::Result<()> {
+    let days = days
+        .unwrap_or(1..=25)
+        .filter(|&day| puzzle_exists(year, day))
+        .collect::<Vec<_>>();
+
+    let (unlocked, locked) = days
+        .into_iter()
+        .partition::<Vec<_>, _>(|&day| unlock_time(year, day) <= Utc::now());
+
+    let mut missing = Vec::new();
+
+    for day in unlocked {
+        if !is_input_cached(year, day)? {
+            missing.push(day);
+        }
+    }
+
+    if !locked.is_empty() {
+        println!("Skipping {} days that have not unlocked yet", locked.len());
+    }
+
+    if missing.is_empty() {
+        println!("No inputs to download for {year}");
+        return Ok(());
+    }
+
+    if config()?.offline {
+        let days = missing
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let url = format!("{}/{year}/day/{}/input", config()?.url, missing[0]);
+
+        return Err(anyhow::Error::from(Error::Offline { url }).context(format!(
+            "the inputs for {year} days {days} are not cached and requests are disabled in \
+             offline mode"
+        )));
+    }
+
+    let mut failed = Vec::new();
+
+    for (i, &day) in missing.iter().enumerate() {
+        let progress = format!("[{}/{}] {year} day {day}", i + 1, missing.len());
+
+        match request_wait()? {
+            Some(wait) => println!("{progress}: scheduled in {wait:#?}"),
+            None => println!("{progress}: downloading"),
+        }
+
+        // keep downloading the other days, reporting every failure at the end
+        if let Err(e) = load_input(year, day) {
+            eprintln!("{progress}: failed: {e}");
+            failed.push((day, e));
+        }
+    }
+
+    println!(
+        "Downloaded {} of {} inputs for {year}",
+        missing.len() - failed.len(),
+        missing.len()
+    );
+
+    let days = failed
+        .iter()
+        .map(|(day, _)| day.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match failed.into_iter().next() {
+        // the first error decides the exit code
+        Some((_, e)) => Err(anyhow::Error::from(e).context(format!(
+            "failed to download the inputs for {year} days {days}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Cli, clap::Error> {
+        Cli::try_parse_args(args.split_whitespace())
+    }
+
+    #[test]
+    fn global_options() {
+        for args in [
+            "aoc --offline status",
+            "aoc status --offline",
+            "aoc --profile bob fetch -y 2024",
+            "aoc fetch -y 2024 --profile bob",
+            "aoc --cache /x read -y 2024 -d 1",
+            "aoc read --cache /x -y 2024 -d 1",
+        ] {
+            let cli = Cli::try_parse_from(args.split_whitespace()).unwrap();
+            assert!(cli.command.is_some() && cli.puzzle.is_none(), "{args}");
+            assert!(parse(args).is_ok(), "{args}");
+        }
+
+        let cli = parse("aoc --offline -y 2024 -d 1 -i").unwrap();
+        assert!(cli.config.offline && cli.puzzle.is_some() && cli.command.is_none());
+
+        assert!(parse("aoc -y 2024 -d 1 status").is_err());
+        assert!(parse("aoc --offline").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::{Error, config, download_cached, profile_cache};
+
+/// Age of a cached calendar page before the stars are downloaded again.
+const CALENDAR_TTL: Duration = Duration::from_mins(15);
+
+/// Two header lines numbering the columns of the days of a year, the tens above the ones.
+pub fn day_header(days: u8) -> [String; 2] {
+    let tens = (1..=days)
+        .map(|day| {
+            if day < 10 {
+                ' '
+            } else {
+                char::from(b'0' + day / 10)
+            }
+        })
+        .collect();
+
+    let ones = (1..=days).map(|day| char::from(b'0' + day % 10)).collect();
+
+    [tens, ones]
+}
+
+/// Parses the stars earned on each day from the calendar page of a year, using the
+/// `calendar-complete` (one star) and `calendar-verycomplete` (two stars) classes of each day link.
+pub fn parse_calendar_stars(html: &str) -> BTreeMap<u8, u8> {
+    html.split("<a ")
+        .skip(1)
+        .filter_map(|tag| {
+            let tag = tag.split_once('>').map_or(tag, |(tag, _)| tag);
+            let (_, classes) = tag.split_once("class=\"")?;
+            let classes = classes
+                .split_once('"')
+                .map_or(classes, |(classes, _)| classes);
+
+            let day = classes
+                .split_whitespace()
+                .find_map(|class| class.strip_prefix("calendar-day")?.parse::<u8>().ok())?;
+
+            let stars = classes
+                .split_whitespace()
+                .map(|class| match class {
+                    "calendar-verycomplete" => 2,
+                    "calendar-complete" => 1,
+                    _ => 0,
+                })
+                .max()
+                .unwrap_or_default();
+
+            Some((day, stars))
+        })
+        .collect()
+}
+
+/// Whether a page was served to a logged in session, which the header shows with a log out link.
+/// A rejected session still gets the calendar, only without any stars.
+fn logged_in(html: &str) -> bool {
+    html.contains("/auth/logout\"")
+}
+
+/// Loads the calendar page of a year, cached for a while between downloads, and returns the stars
+/// earned on each day.
+pub fn load_calendar_stars(year: u16) -> Result<BTreeMap<u8, u8>, Error> {
+    let path = profile_cache()?.join(format!("calendar-{year}.html"));
+    download_cached(
+        &format!("{}/{year}", config()?.url),
+        &path,
+        CALENDAR_TTL,
+        |page| {
+            if logged_in(page) {
+                Ok(parse_calendar_stars(page))
+            } else {
+                Err(Error::Unauthorised { status: 200 })
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<a href="/2024/auth/logout">[Log Out]</a>
+<pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2024/day/1" class="calendar-day1 calendar-verycomplete">
+<span class="calendar-day"> 1</span></a>
+<a aria-label="Day 2, one star" href="/2024/day/2" class="calendar-day2 calendar-complete">
+<span class="calendar-day"> 2</span></a>
+<a aria-label="Day 3" href="/2024/day/3" class="calendar-day3">
+<span class="calendar-day"> 3</span></a>
+<a href="/2024/support">[Support]</a>
+</pre>"#;
+
+    #[test]
+    fn calendar_stars() {
+        assert_eq!(
+            parse_calendar_stars(PAGE),
+            BTreeMap::from([(1, 2), (2, 1), (3, 0)])
+        );
+    }
+
+    #[test]
+    fn logged_out() {
+        assert!(logged_in(PAGE));
+        assert!(!logged_in(&PAGE.replace("logout", "login")));
+    }
+
+    #[test]
+    fn header() {
+        assert_eq!(
+            day_header(12),
+            ["         111".to_string(), "123456789012".to_string()]
+        );
+    }
+}
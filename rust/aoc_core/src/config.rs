@@ -0,0 +1,259 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::{Error, validate_profile};
+
+const DEFAULT_URL: &str = "https://adventofcode.com";
+const DEFAULT_USER_AGENT: &str = "github.com/ndunnett/aoc/rust";
+const DEFAULT_MAX_REQUESTS: usize = 5; // 5 requests per request period
+const DEFAULT_REQUEST_PERIOD: Duration = Duration::from_mins(15); // request period duration
+const CONFIG_FILE: &str = "aoc.toml";
+const ENV_FILE: &str = ".env";
+
+/// Root of the repository, the nearest directory above the working directory that holds the Rust
+/// workspace, so that installed or relocated binaries find it too. Falls back to where this crate
+/// was built when run from outside the repository.
+pub(crate) fn repository() -> PathBuf {
+    let found = env::current_dir().ok().and_then(|cwd| {
+        cwd.ancestors()
+            .find(|dir| dir.join("rust/Cargo.toml").is_file())
+            .map(Path::to_path_buf)
+    });
+
+    found.unwrap_or_else(|| {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        manifest
+            .ancestors()
+            .nth(2)
+            .map_or_else(|| manifest.join("../.."), Path::to_path_buf)
+    })
+}
+
+/// Looks up a variable in the environment, falling back to the `.env` file at the repository root.
+pub fn env_var(name: &str) -> Option<String> {
+    static ENV_FILE_VARS: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+    env::var(name).ok().or_else(|| {
+        ENV_FILE_VARS
+            .get_or_init(|| {
+                fs::read_to_string(repository().join(ENV_FILE))
+                    .map(|content| parse_env_file(&content))
+                    .unwrap_or_default()
+            })
+            .get(name)
+            .cloned()
+    })
+}
+
+fn parse_env_file(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.trim_start_matches("export ").split_once('=')?;
+            let value = value.trim();
+
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// One layer of configuration, where unset values fall through to the layer below.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub url: Option<String>,
+    pub cache: Option<PathBuf>,
+    pub user_agent: Option<String>,
+    pub max_requests: Option<usize>,
+    /// Request period in seconds
+    pub request_period: Option<u64>,
+    /// Fail instead of making requests for anything that is not cached
+    pub offline: Option<bool>,
+    /// Session profile, with its own session variable and cache directory
+    pub profile: Option<String>,
+}
+
+impl Settings {
+    fn from_file(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| Error::Config(format!("failed to read {path:?}: {e}")))?;
+
+        let mut settings = toml::from_str::<Self>(&content)
+            .map_err(|e| Error::Config(format!("failed to parse {path:?}: {e}")))?;
+
+        // relative cache paths are relative to the config file
+        if let (Some(cache), Some(parent)) = (&settings.cache, path.parent()) {
+            settings.cache = Some(parent.join(cache));
+        }
+
+        Ok(settings)
+    }
+
+    fn from_env() -> Result<Self, Error> {
+        fn parse<T: std::str::FromStr>(name: &str) -> Result<Option<T>, Error> {
+            env_var(name)
+                .map(|value| {
+                    value
+                        .parse()
+                        .map_err(|_| Error::Config(format!("{name} has invalid value {value:?}")))
+                })
+                .transpose()
+        }
+
+        Ok(Self {
+            url: env_var("AOC_URL"),
+            cache: env_var("AOC_CACHE").map(PathBuf::from),
+            user_agent: env_var("AOC_USER_AGENT"),
+            max_requests: parse("AOC_MAX_REQUESTS")?,
+            request_period: parse("AOC_REQUEST_PERIOD")?,
+            offline: env_var("AOC_OFFLINE")
+                .map(|value| match value.to_lowercase().as_str() {
+                    "1" | "true" | "yes" => Ok(true),
+                    "" | "0" | "false" | "no" => Ok(false),
+                    _ => Err(Error::Config(format!(
+                        "AOC_OFFLINE has invalid value {value:?}"
+                    ))),
+                })
+                .transpose()?,
+            profile: env_var("AOC_PROFILE"),
+        })
+    }
+
+    /// Overrides the values in this layer with any values set in `other`.
+    fn merge(self, other: Self) -> Self {
+        Self {
+            url: other.url.or(self.url),
+            cache: other.cache.or(self.cache),
+            user_agent: other.user_agent.or(self.user_agent),
+            max_requests: other.max_requests.or(self.max_requests),
+            request_period: other.request_period.or(self.request_period),
+            offline: other.offline.or(self.offline),
+            profile: other.profile.or(self.profile),
+        }
+    }
+}
+
+/// Resolved configuration, layered from defaults, the config file, environment variables and
+/// command line overrides.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub url: String,
+    pub cache: PathBuf,
+    pub user_agent: String,
+    pub max_requests: usize,
+    pub request_period: Duration,
+    pub offline: bool,
+    pub profile: Option<String>,
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+impl Config {
+    fn load(overrides: Settings) -> Result<Self, Error> {
+        let path = env_var("AOC_CONFIG")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| repository().join("rust").join(CONFIG_FILE));
+
+        let file = if path.is_file() {
+            Settings::from_file(&path)?
+        } else {
+            Settings::default()
+        };
+
+        let settings = file.merge(Settings::from_env()?).merge(overrides);
+
+        let config = Self {
+            url: settings
+                .url
+                .unwrap_or_else(|| DEFAULT_URL.to_string())
+                .trim_end_matches('/')
+                .to_string(),
+            cache: settings
+                .cache
+                .unwrap_or_else(|| repository().join(".cache")),
+            user_agent: settings
+                .user_agent
+                .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
+            max_requests: settings.max_requests.unwrap_or(DEFAULT_MAX_REQUESTS).max(1),
+            request_period: settings
+                .request_period
+                .map_or(DEFAULT_REQUEST_PERIOD, Duration::from_secs),
+            offline: settings.offline.unwrap_or_default(),
+            profile: settings.profile.filter(|profile| !profile.is_empty()),
+        };
+
+        if let Some(profile) = &config.profile {
+            validate_profile(profile)?;
+        }
+
+        fs::create_dir_all(&config.cache).map_err(Error::cache(&config.cache))?;
+
+        Ok(config)
+    }
+
+    /// Environment variables that reproduce this configuration in a child process.
+    pub fn env(&self) -> [(&'static str, String); 7] {
+        [
+            ("AOC_URL", self.url.clone()),
+            ("AOC_CACHE", self.cache.to_string_lossy().to_string()),
+            ("AOC_USER_AGENT", self.user_agent.clone()),
+            ("AOC_MAX_REQUESTS", self.max_requests.to_string()),
+            (
+                "AOC_REQUEST_PERIOD",
+                self.request_period.as_secs().to_string(),
+            ),
+            ("AOC_OFFLINE", self.offline.to_string()),
+            ("AOC_PROFILE", self.profile.clone().unwrap_or_default()),
+        ]
+    }
+}
+
+/// Initialises the configuration with command line overrides, before anything reads it.
+pub fn configure(overrides: Settings) -> Result<(), Error> {
+    CONFIG
+        .set(Config::load(overrides)?)
+        .map_err(|_| Error::Config("configuration has already been initialised".to_string()))
+}
+
+/// Returns the configuration, loading it without overrides if it has not been initialised.
+pub fn config() -> Result<&'static Config, Error> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+
+    let config = Config::load(Settings::default())?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn env_file() {
+        let vars = parse_env_file(
+            "# comment\n\nAOC_SESSION=abc123\nexport AOC_URL = \"http://localhost:8080\"\nAOC_USER_AGENT='me'\n",
+        );
+
+        assert_eq!(vars.len(), 3);
+        assert_eq!(vars["AOC_SESSION"], "abc123");
+        assert_eq!(vars["AOC_URL"], "http://localhost:8080");
+        assert_eq!(vars["AOC_USER_AGENT"], "me");
+    }
+}
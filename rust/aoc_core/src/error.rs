@@ -0,0 +1,79 @@
+use std::{io, path::PathBuf, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+/// Failures talking to the Advent of Code website or managing the local cache.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{var} is not set, add it to the environment or to .env at the repository root")]
+    MissingSession { var: String },
+
+    #[error("the session was rejected with status {status}, it may have expired")]
+    Unauthorised { status: u16 },
+
+    #[error("{url} is not available yet, the puzzle may still be locked")]
+    Locked { url: String },
+
+    #[error("{url} was not found")]
+    NotFound { url: String },
+
+    #[error("requests are being rate limited by the server{}", retry_message(*.retry_after))]
+    Throttled { retry_after: Option<Duration> },
+
+    #[error("{year} day {day} unlocks at {unlock}, wait for it with `aoc wait`")]
+    NotUnlocked {
+        year: u16,
+        day: u8,
+        unlock: DateTime<Utc>,
+    },
+
+    #[error("there is no puzzle for {year} day {day}")]
+    NoSuchPuzzle { year: u16, day: u8 },
+
+    #[error("request to {url} failed with status {status}")]
+    Http { url: String, status: u16 },
+
+    #[error("request to {url} failed: {source}")]
+    Network { url: String, source: ureq::Error },
+
+    #[error("failed to access cache file {path:?}: {source}")]
+    Cache { path: PathBuf, source: io::Error },
+
+    #[error(
+        "rejected the input for {year} day {day} because {reason}, it was moved to {quarantine:?}"
+    )]
+    InvalidInput {
+        year: u16,
+        day: u8,
+        reason: String,
+        quarantine: PathBuf,
+    },
+
+    #[error("{url} is not cached and requests are disabled in offline mode")]
+    Offline { url: String },
+
+    #[error("invalid configuration: {0}")]
+    Config(String),
+}
+
+fn retry_message(retry_after: Option<Duration>) -> String {
+    retry_after
+        .map(|duration| format!(", retry after {duration:#?}"))
+        .unwrap_or_default()
+}
+
+impl Error {
+    pub(crate) fn cache(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| Self::Cache { path, source }
+    }
+
+    /// Reads a missing puzzle page as one that is still locked, for requests made once the
+    /// puzzle should have unlocked.
+    pub(crate) fn locked(self) -> Self {
+        match self {
+            Self::NotFound { url } => Self::Locked { url },
+            error => error,
+        }
+    }
+}
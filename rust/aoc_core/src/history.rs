@@ -0,0 +1,235 @@
+use std::{collections::BTreeMap, fs, io::Write, process, time::Duration};
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Anyhow, Report, get_cache, puzzle_from_path};
+
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// Suffix of the commit recorded for runs from a working tree with uncommitted changes.
+const DIRTY_SUFFIX: &str = "-dirty";
+
+/// Environment variable used to label recorded runs as a named baseline.
+pub const BASELINE_VAR: &str = "AOC_BASELINE";
+
+/// A single recorded benchmark run, stored as one line of JSON in the history file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub commit: Option<String>,
+    pub baseline: Option<String>,
+    pub release: bool,
+    pub year: u16,
+    pub day: u8,
+    pub report: Report,
+}
+
+/// Resolves a git reference to a short commit hash.
+pub fn git_commit(reference: &str) -> Option<String> {
+    let output = process::Command::new("git")
+        .args(["rev-parse", "--short", reference])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+/// Commit of the working tree, marked as dirty when there are uncommitted changes.
+pub fn head_commit() -> Option<String> {
+    let commit = git_commit("HEAD")?;
+
+    let dirty = process::Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+
+    Some(if dirty {
+        format!("{commit}{DIRTY_SUFFIX}")
+    } else {
+        commit
+    })
+}
+
+/// Appends a run to the history, with the commit resolved once by the caller.
+pub fn record_history(
+    path: &str,
+    release: bool,
+    commit: Option<&str>,
+    report: &Report,
+) -> Anyhow<()> {
+    let (year, day) = puzzle_from_path(path)?;
+
+    let entry = HistoryEntry {
+        timestamp: Utc::now(),
+        commit: commit.map(String::from),
+        baseline: std::env::var(BASELINE_VAR).ok(),
+        release,
+        year,
+        day,
+        report: report.clone(),
+    };
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_cache()?.join(HISTORY_FILE))?;
+
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+/// Loads every recorded run, skipping lines that fail to parse.
+pub fn load_history() -> Anyhow<Vec<HistoryEntry>> {
+    let path = get_cache()?.join(HISTORY_FILE);
+
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    Ok(parse_history(&fs::read_to_string(path)?))
+}
+
+fn parse_history(content: &str) -> Vec<HistoryEntry> {
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Latest release run of each day of a year recorded before `started`, for a named baseline or
+/// else for a commit. Runs from a working tree with uncommitted changes are never matched by
+/// commit, since they do not reflect it.
+pub fn baseline_reports(
+    history: Vec<HistoryEntry>,
+    year: u16,
+    reference: &str,
+    commit: Option<&str>,
+    started: DateTime<Utc>,
+) -> Anyhow<BTreeMap<u8, Report>> {
+    let history = history
+        .into_iter()
+        .filter(|entry| entry.year == year && entry.release && entry.timestamp < started)
+        .collect::<Vec<_>>();
+
+    let labelled = history
+        .iter()
+        .any(|entry| entry.baseline.as_deref() == Some(reference));
+
+    // history is in chronological order, so later runs overwrite earlier ones
+    let previous = history
+        .iter()
+        .filter(|entry| {
+            if labelled {
+                entry.baseline.as_deref() == Some(reference)
+            } else {
+                commit.is_some() && entry.commit.as_deref() == commit
+            }
+        })
+        .map(|entry| (entry.day, entry.report.clone()))
+        .collect::<BTreeMap<_, _>>();
+
+    if !previous.is_empty() {
+        return Ok(previous);
+    }
+
+    let dirty = commit.is_some_and(|commit| {
+        history
+            .iter()
+            .any(|entry| entry.commit == Some(format!("{commit}{DIRTY_SUFFIX}")))
+    });
+
+    if dirty {
+        Err(anyhow!(
+            "only runs with uncommitted changes were recorded for {reference} in {year}, \
+            label a baseline with --save-baseline to compare against them"
+        ))
+    } else {
+        Err(anyhow!(
+            "no benchmark history found for {reference} in {year}"
+        ))
+    }
+}
+
+/// How the duration of a stage changed between two runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Faster,
+    Slower,
+    Noise,
+}
+
+/// Percentage change from one duration to another, and whether it is beyond the noise threshold.
+pub fn compare_durations(before: Duration, after: Duration, threshold: f64) -> (f64, Change) {
+    let change = (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+
+    let verdict = if change <= -threshold {
+        Change::Faster
+    } else if change >= threshold {
+        Change::Slower
+    } else {
+        Change::Noise
+    };
+
+    (change, verdict)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(day: u8, commit: &str, baseline: Option<&str>, total_ns: u64) -> String {
+        format!(
+            r#"{{"timestamp":"2024-12-0{day}T00:00:00Z","commit":"{commit}","baseline":{},"release":true,"year":2024,"day":{day},"report":{{"build":{{"duration_ns":1,"iterations":1}},"parts":[],"total_ns":{total_ns}}}}}"#,
+            baseline.map_or("null".to_string(), |name| format!("{name:?}"))
+        )
+    }
+
+    #[test]
+    fn baselines() {
+        let content = [
+            entry(1, "abc1234", None, 100),
+            "not json".to_string(),
+            entry(1, "abc1234", None, 200),
+            entry(2, "abc1234", Some("fast"), 300),
+            entry(3, "def5678-dirty", None, 400),
+        ]
+        .join("\n");
+
+        let history = parse_history(&content);
+        assert_eq!(history.len(), 4);
+
+        let started = "2025-01-01T00:00:00Z".parse().unwrap();
+        let reports =
+            |reference, commit| baseline_reports(history.clone(), 2024, reference, commit, started);
+
+        let previous = reports("abc1234", Some("abc1234")).unwrap();
+        assert_eq!(previous.keys().copied().collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(previous[&1].total_ns, 200);
+
+        let previous = reports("fast", None).unwrap();
+        assert_eq!(previous.keys().copied().collect::<Vec<_>>(), [2]);
+
+        let dirty = reports("def5678", Some("def5678")).unwrap_err();
+        assert!(dirty.to_string().contains("uncommitted changes"));
+        assert!(reports("main", None).is_err());
+    }
+
+    #[test]
+    fn thresholds() {
+        let ms = Duration::from_millis;
+
+        assert_eq!(compare_durations(ms(100), ms(104), 5.0).1, Change::Noise);
+        assert_eq!(compare_durations(ms(100), ms(95), 5.0).1, Change::Faster);
+        assert_eq!(
+            compare_durations(ms(100), ms(200), 5.0),
+            (100.0, Change::Slower)
+        );
+        assert_eq!(compare_durations(ms(100), ms(106), 5.0).1, Change::Slower);
+    }
+}
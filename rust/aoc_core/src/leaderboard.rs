@@ -0,0 +1,193 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, Local};
+use colored::Colorize;
+use serde::Deserialize;
+
+use crate::{Anyhow, Error, config, day_header, days_in_year, download_cached, profile_cache};
+
+/// Minimum age of a cached leaderboard before it is downloaded again, as the API asks for.
+const LEADERBOARD_TTL: Duration = Duration::from_mins(15);
+
+/// Private leaderboard as returned by the JSON API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    /// Stars earned, keyed by day and then part
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Time the given star was earned, if it has been.
+    pub fn star_time(&self, day: u8, part: u8) -> Option<DateTime<Local>> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        Some(DateTime::from_timestamp(star.get_star_ts, 0)?.with_timezone(&Local))
+    }
+}
+
+impl Leaderboard {
+    /// Members ranked by local score, with ties going to whoever reached it first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+        });
+
+        members
+    }
+}
+
+/// Loads a private leaderboard, reusing the cached copy until it is old enough to refresh.
+pub fn load_leaderboard(year: u16, id: u64) -> Anyhow<Leaderboard> {
+    let path = profile_cache()?.join(format!("leaderboard-{year}-{id}.json"));
+
+    let url = format!(
+        "{}/{year}/leaderboard/private/view/{id}.json",
+        config()?.url
+    );
+    // the API answers a rejected session with the login page rather than an error status
+    let leaderboard = download_cached(&url, &path, LEADERBOARD_TTL, |json| {
+        serde_json::from_str(json).map_err(|_| Error::Unauthorised { status: 200 })
+    });
+
+    match leaderboard {
+        Err(e @ Error::NotFound { .. }) => Err(anyhow::Error::from(e).context(format!(
+            "leaderboard {id} for {year} was not found, check the id and that the session can \
+             view it"
+        ))),
+        leaderboard => Ok(leaderboard?),
+    }
+}
+
+/// Renders a ranked table of members with their stars for each day, where `*` marks both stars and
+/// `+` only the first, and the time of their last star.
+pub fn render_leaderboard(leaderboard: &Leaderboard, year: u16) -> String {
+    let days = days_in_year(year);
+    let members = leaderboard.ranked();
+
+    let [tens, ones] = day_header(days);
+
+    let mut out = format!(
+        "{:18}{tens}\n{:>4} {:>5} {:>5}  {ones}  Last star\n",
+        "", "Rank", "Score", "Stars"
+    );
+
+    for (i, member) in members.iter().enumerate() {
+        let stars = (1..=days)
+            .map(|day| {
+                match (member.star_time(day, 1), member.star_time(day, 2)) {
+                    (Some(_), Some(_)) => "*".bright_yellow(),
+                    (Some(_), None) => "+".bright_white(),
+                    _ => ".".dimmed(),
+                }
+                .to_string()
+            })
+            .collect::<String>();
+
+        let last = DateTime::from_timestamp(member.last_star_ts, 0)
+            .filter(|_| member.stars > 0)
+            .map(|time| time.with_timezone(&Local).format("%F %T").to_string())
+            .unwrap_or_else(|| "-".to_string());
+
+        out.push_str(&format!(
+            "{:>4} {:>5} {:>5}  {stars}  {last:<19}  {}\n",
+            format!("{})", i + 1),
+            member.local_score,
+            member.stars,
+            member.display_name()
+        ));
+    }
+
+    out.trim_end().to_string()
+}
+
+/// Renders the times each member earned the stars of a day, in the order they finished.
+pub fn render_leaderboard_day(leaderboard: &Leaderboard, day: u8) -> String {
+    let format = |time: Option<DateTime<Local>>| {
+        time.map_or_else(|| "-".to_string(), |time| time.format("%F %T").to_string())
+    };
+
+    let mut members = leaderboard
+        .members
+        .values()
+        .filter(|member| member.star_time(day, 1).is_some())
+        .collect::<Vec<_>>();
+
+    members.sort_by_key(|member| {
+        let (first, second) = (member.star_time(day, 1), member.star_time(day, 2));
+        (second.is_none(), second, first)
+    });
+
+    let mut out = format!("{:>4}  {:<19}  {:<19}  Name\n", "Rank", "Part 1", "Part 2");
+
+    for (i, member) in members.iter().enumerate() {
+        out.push_str(&format!(
+            "{:>4}  {:<19}  {:<19}  {}\n",
+            format!("{})", i + 1),
+            format(member.star_time(day, 1)),
+            format(member.star_time(day, 2)),
+            member.display_name()
+        ));
+    }
+
+    out.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const JSON: &str = r#"{"event":"2025","owner_id":1,"day1_ts":1764565200,"members":{
+"1":{"id":1,"name":"alice","stars":3,"local_score":10,"global_score":0,"last_star_ts":1764651600,
+"completion_day_level":{"1":{"1":{"get_star_ts":1764565300,"star_index":1},"2":{"get_star_ts":1764565400,"star_index":2}},
+"2":{"1":{"get_star_ts":1764651600,"star_index":3}}}},
+"2":{"id":2,"name":null,"stars":0,"local_score":0,"global_score":0,"last_star_ts":0,"completion_day_level":{}}}}"#;
+
+    #[test]
+    fn leaderboard() {
+        colored::control::set_override(false);
+        let leaderboard = serde_json::from_str::<Leaderboard>(JSON).unwrap();
+        let table = render_leaderboard(&leaderboard, 2025);
+        let lines = table.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("Rank Score Stars  123456789012  Last star"));
+        assert!(lines[2].starts_with("  1)    10     3  *+..........  "));
+        assert!(lines[2].ends_with("  alice"));
+        assert!(lines[3].ends_with("-                    (anonymous user #2)"));
+
+        let day = render_leaderboard_day(&leaderboard, 2);
+        assert_eq!(day.lines().count(), 2);
+        assert!(
+            day.lines()
+                .nth(1)
+                .unwrap()
+                .ends_with("  -                    alice")
+        );
+    }
+}
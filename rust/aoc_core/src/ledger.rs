@@ -0,0 +1,127 @@
+use std::{collections::BTreeMap, fs};
+
+use crate::{Anyhow, profile_cache};
+
+const ANSWERS_FILE: &str = "answers.txt";
+
+/// Accepted answers keyed by (year, day, part).
+pub type Answers = BTreeMap<(u16, u8, u8), String>;
+
+pub fn load_answers() -> Anyhow<Answers> {
+    let path = profile_cache()?.join(ANSWERS_FILE);
+
+    if !path.is_file() {
+        return Ok(Answers::new());
+    }
+
+    parse_answers(&fs::read_to_string(path)?)
+}
+
+pub fn record_answer(year: u16, day: u8, part: u8, answer: &str) -> Anyhow<()> {
+    let mut answers = load_answers()?;
+    answers.insert((year, day, part), answer.to_string());
+    fs::write(
+        profile_cache()?.join(ANSWERS_FILE),
+        format_answers(&answers),
+    )?;
+    Ok(())
+}
+
+/// Parses the lines of the answers file, each `{year} {day} {part} {answer}` with line breaks and
+/// backslashes in the answer escaped.
+fn parse_answers(content: &str) -> Anyhow<Answers> {
+    content
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut fields = line.splitn(4, ' ');
+
+            let mut next = || {
+                fields
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("malformed line in {ANSWERS_FILE}: {line}"))
+            };
+
+            let year = next()?.parse::<u16>()?;
+            let day = next()?.parse::<u8>()?;
+            let part = next()?.parse::<u8>()?;
+            let answer = unescape(next()?);
+            Ok(((year, day, part), answer))
+        })
+        .collect()
+}
+
+fn format_answers(answers: &Answers) -> String {
+    answers
+        .iter()
+        .map(|((year, day, part), answer)| format!("{year} {day:02} {part} {}\n", escape(answer)))
+        .collect()
+}
+
+/// Escapes an answer to fit on one line, e.g. one spanning several lines of ASCII art.
+fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let answers = Answers::from([
+            ((2024, 5, 1), "143".to_string()),
+            ((2024, 5, 2), "123".to_string()),
+            ((2023, 12, 1), "HELLO WORLD".to_string()),
+        ]);
+
+        let content = format_answers(&answers);
+        assert!(content.starts_with("2023 12 1 HELLO WORLD\n2024 05 1 143\n"));
+        assert_eq!(parse_answers(&content).unwrap(), answers);
+        assert_eq!(parse_answers("\n2024 05 1 143\n\n").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn multiline_answers() {
+        let answers = Answers::from([
+            ((2022, 10, 2), "#..#\n####\r\n".to_string()),
+            ((2022, 10, 1), "a\\nb\\".to_string()),
+        ]);
+
+        let content = format_answers(&answers);
+        assert_eq!(content.lines().count(), 2);
+        assert_eq!(parse_answers(&content).unwrap(), answers);
+    }
+
+    #[test]
+    fn malformed_lines() {
+        assert!(parse_answers("2024 05 1").is_err());
+        assert!(parse_answers("2024 05 x 143").is_err());
+        assert!(parse_answers("2024 300 1 143").is_err());
+        assert!(parse_answers("2024 05 1 143\ngarbage").is_err());
+    }
+}
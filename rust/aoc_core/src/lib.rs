@@ -1,137 +1,259 @@
-use std::{env, fs, path::PathBuf, sync::OnceLock, time::Duration};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use anyhow::anyhow;
-use chrono::{DateTime, Utc};
+use chrono::Utc;
+
+mod calendar;
+pub use calendar::*;
+
+mod config;
+pub use config::*;
+
+mod error;
+pub use error::*;
+
+mod history;
+pub use history::*;
+
+mod leaderboard;
+pub use leaderboard::*;
+
+mod ledger;
+pub use ledger::*;
+
+mod profile;
+pub use profile::*;
+
+mod puzzle;
+pub use puzzle::*;
+
+mod render;
+pub use render::*;
+
+mod report;
+pub use report::*;
+
+mod schedule;
+pub use schedule::*;
+
+mod stats;
+pub use stats::*;
+
+mod submit;
+pub use submit::*;
+
+mod template;
+pub use template::*;
+
+mod throttle;
+pub use throttle::*;
+
+mod validate;
+pub use validate::*;
+
+mod workspace;
+pub use workspace::*;
 
 type Anyhow<T> = anyhow::Result<T>;
 
-const MAX_REQUESTS: usize = 5; // 5 requests per request period
-const REQUEST_PERIOD: u64 = Duration::from_mins(15).as_secs(); // request period duration
-const USER_AGENT: &str = "github.com/ndunnett/aoc/rust";
-const LOG_FILE: &str = "log.txt";
+const MAX_RETRIES: u32 = 3; // retries after the server responds with 429 Too Many Requests
+const BACKOFF: Duration = Duration::from_mins(1); // initial backoff without a Retry-After header
+
+pub(crate) fn get_cache() -> Result<&'static PathBuf, Error> {
+    Ok(&config()?.cache)
+}
+
+fn get_session() -> Result<String, Error> {
+    let var = session_var(config()?.profile.as_deref());
+
+    env_var(&var)
+        .filter(|session| !session.is_empty())
+        .map(|session| format!("session={session}"))
+        .ok_or(Error::MissingSession { var })
+}
+
+fn agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .build()
+        .into()
+}
+
+fn read_response(url: &str, response: ureq::http::Response<ureq::Body>) -> Result<String, Error> {
+    let url = url.to_string();
+    let status = response.status().as_u16();
+
+    match status {
+        200..=299 => response
+            .into_body()
+            .read_to_string()
+            .map_err(|source| Error::Network { url, source }),
+        400 | 401 | 403 => Err(Error::Unauthorised { status }),
+        404 => Err(Error::NotFound { url }),
+        429 => Err(Error::Throttled {
+            retry_after: response
+                .headers()
+                .get("Retry-After")
+                .and_then(|value| parse_retry_after(value.to_str().ok()?)),
+        }),
+        _ => Err(Error::Http { url, status }),
+    }
+}
+
+/// Sends a throttled request, backing off and retrying when the server responds with 429.
+fn request(
+    url: &str,
+    send: impl Fn(&str) -> Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String, Error> {
+    if config()?.offline {
+        return Err(Error::Offline {
+            url: url.to_string(),
+        });
+    }
 
-pub const AOC_URL: &str = "https://adventofcode.com";
+    let session = get_session()?;
+    let mut attempt = 0;
 
-fn get_cache() -> &'static PathBuf {
-    static CACHE_PATH: OnceLock<PathBuf> = OnceLock::new();
+    loop {
+        throttle_requests()?;
 
-    CACHE_PATH.get_or_init(|| {
-        let path = env::current_exe()
-            .expect("failed to get the current executable path")
-            .parent()
-            .expect("failed to get the current executable parent path")
-            .join("../../../.cache");
+        let response = send(&session).map_err(|source| Error::Network {
+            url: url.to_string(),
+            source,
+        })?;
 
-        if let Err(e) = fs::create_dir_all(&path) {
-            panic!("failed to create cache directory {path:?}: {e}");
+        match read_response(url, response) {
+            Err(Error::Throttled { retry_after }) if attempt < MAX_RETRIES => {
+                let wait = retry_after.unwrap_or(BACKOFF * 2_u32.pow(attempt));
+                println!("Rate limited by the server, retrying in {wait:#?}.");
+                record_retry_after(wait)?;
+                attempt += 1;
+            }
+            result => return result,
         }
+    }
+}
+
+pub(crate) fn download_file(url: &str) -> Result<String, Error> {
+    let user_agent = &config()?.user_agent;
 
-        path
+    request(url, |session| {
+        agent()
+            .get(url)
+            .header("Cookie", session)
+            .header("User-Agent", user_agent)
+            .call()
     })
 }
 
-fn get_session() -> &'static String {
-    static SESSION: OnceLock<String> = OnceLock::new();
+/// Downloads a page, reusing the copy cached at `path` until it is `ttl` old. A stale copy is still
+/// used in offline mode rather than failing. A downloaded page is only cached once `parse` accepts
+/// it, so an error page never replaces a good copy.
+pub(crate) fn download_cached<T>(
+    url: &str,
+    path: &Path,
+    ttl: Duration,
+    parse: impl Fn(&str) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let age = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok());
+
+    if age.is_some_and(|age| age < ttl || config().is_ok_and(|c| c.offline)) {
+        return parse(&fs::read_to_string(path).map_err(Error::cache(path))?);
+    }
+
+    let page = download_file(url)?;
+    let parsed = parse(&page)?;
+    fs::write(path, &page).map_err(Error::cache(path))?;
+    Ok(parsed)
+}
+
+pub(crate) fn post_form(url: &str, form: &[(&str, &str)]) -> Result<String, Error> {
+    let user_agent = &config()?.user_agent;
 
-    SESSION.get_or_init(|| {
-        format!(
-            "session={}",
-            env::var("AOC_SESSION").expect("failed to get AOC_SESSION environment variable")
-        )
+    request(url, |session| {
+        agent()
+            .post(url)
+            .header("Cookie", session)
+            .header("User-Agent", user_agent)
+            .send_form(form.iter().copied())
     })
 }
 
-fn throttle_requests() -> Anyhow<()> {
-    let path = get_cache().join(LOG_FILE);
-
-    if path.is_file() {
-        let timestamps = {
-            fs::read_to_string(&path)?
-                .lines()
-                .map(|line| DateTime::parse_from_rfc3339(line).map(|dt| dt.with_timezone(&Utc)))
-                .collect::<Result<Vec<_>, chrono::ParseError>>()
-        }?;
-
-        let now = Utc::now();
-
-        if timestamps.len() >= MAX_REQUESTS {
-            let elapsed = now
-                .signed_duration_since(timestamps[0])
-                .num_seconds()
-                .max(0) as u64;
-
-            if elapsed < REQUEST_PERIOD {
-                let duration = Duration::from_secs(REQUEST_PERIOD - elapsed);
-                println!("Request throttled, waiting {duration:#?} before downloading input file.");
-                std::thread::sleep(duration);
-            }
-        }
+pub fn is_input_cached(year: u16, day: u8) -> Result<bool, Error> {
+    Ok(profile_cache()?
+        .join(format!("input-{year}-{day:02}.txt"))
+        .is_file())
+}
 
-        let now = Utc::now();
-
-        let content = timestamps
-            .into_iter()
-            .chain([now])
-            .filter_map(|t| {
-                if (now.signed_duration_since(t).num_seconds().max(0) as u64) < REQUEST_PERIOD {
-                    Some(t.to_rfc3339())
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        fs::write(path, content)?;
-    } else {
-        fs::write(path, Utc::now().to_rfc3339())?;
-    }
+/// Loads the input from the cache or downloads it, quarantining any input that looks like an
+/// error page or was fetched before the puzzle unlocked.
+pub fn load_input(year: u16, day: u8) -> Result<String, Error> {
+    read_input(year, day, false)
+}
 
-    Ok(())
+/// Downloads the input again, replacing the cached file only once the new input passes the same
+/// checks, so a failed download keeps the old copy.
+pub fn reload_input(year: u16, day: u8) -> Result<String, Error> {
+    read_input(year, day, true)
 }
 
-fn download_file(url: &str) -> Anyhow<String> {
-    throttle_requests()?;
+fn read_input(year: u16, day: u8, refresh: bool) -> Result<String, Error> {
+    let path = profile_cache()?.join(format!("input-{year}-{day:02}.txt"));
+    let cached = !refresh && path.is_file();
 
-    let response = ureq::get(url)
-        .header("Cookie", get_session())
-        .header("User-Agent", USER_AGENT)
-        .call()?;
+    let (input, fetched) = if cached {
+        let modified = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(Error::cache(&path))?;
 
-    if !response.status().is_success() {
-        Err(anyhow::anyhow!(
-            "Response status code {}: make sure AOC_SESSION is set to a valid session",
-            response.status()
-        ))
+        let input = fs::read_to_string(&path).map_err(Error::cache(&path))?;
+        (input, modified.into())
     } else {
-        Ok(response.into_body().read_to_string()?)
-    }
-}
+        check_unlocked(year, day)?;
+        let url = format!("{}/{year}/day/{day}/input", config()?.url);
+        (download_file(&url).map_err(Error::locked)?, Utc::now())
+    };
+
+    if let Err(reason) = check_input(year, day, &input, fetched) {
+        let quarantine = quarantine_input(year, day, &input)?;
+
+        if cached {
+            fs::remove_file(&path).map_err(Error::cache(&path))?;
+        }
 
-pub fn delete_cached_input(year: u16, day: u8) -> Anyhow<()> {
-    let path = get_cache().join(format!("input-{year}-{day:02}.txt"));
+        return Err(Error::InvalidInput {
+            year,
+            day,
+            reason,
+            quarantine,
+        });
+    }
 
-    if path.is_file() {
-        fs::remove_file(path)?;
+    if !cached {
+        fs::write(&path, &input).map_err(Error::cache(&path))?;
     }
 
-    Ok(())
+    Ok(input)
 }
 
-pub fn load_input(year: u16, day: u8) -> Anyhow<String> {
-    let path = get_cache().join(format!("input-{year}-{day:02}.txt"));
-
-    if !path.is_file() {
-        let url = format!("{}/{year}/day/{day}/input", crate::AOC_URL);
-        let input = download_file(&url)?;
-        fs::write(path, &input)?;
-        Ok(input)
+/// Reads an input file, or standard input when the path is `-`.
+pub fn read_input_file(path: &str) -> Anyhow<String> {
+    if path == "-" {
+        Ok(std::io::read_to_string(std::io::stdin())?)
     } else {
-        Ok(fs::read_to_string(path)?)
+        fs::read_to_string(path).map_err(|e| anyhow!("failed to read input file {path}: {e}"))
     }
 }
 
-pub fn auto_input(path: &str) -> Anyhow<String> {
+/// Extracts the year and day from a solution path such as `2024/src/bin/day01.rs`.
+pub fn puzzle_from_path(path: &str) -> Anyhow<(u16, u8)> {
     let path = PathBuf::from(path);
 
     let day = path
@@ -150,5 +272,10 @@ pub fn auto_input(path: &str) -> Anyhow<String> {
         .to_string_lossy()
         .parse::<u16>()?;
 
-    load_input(year, day)
+    Ok((year, day))
+}
+
+pub fn auto_input(path: &str) -> Anyhow<String> {
+    let (year, day) = puzzle_from_path(path)?;
+    Ok(load_input(year, day)?)
 }
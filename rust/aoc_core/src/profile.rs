@@ -0,0 +1,83 @@
+use std::{fs, path::PathBuf};
+
+use crate::{Error, config, get_cache};
+
+const PROFILES_DIR: &str = "profiles";
+
+/// Checks that a profile name is safe to use as a directory and environment variable name.
+pub fn validate_profile(name: &str) -> Result<(), Error> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(Error::Config(format!(
+            "invalid profile name {name:?}, use letters, digits, `-` and `_`"
+        )))
+    }
+}
+
+/// Environment variable holding the session of a profile, e.g. `AOC_SESSION_ALICE`.
+pub fn session_var(profile: Option<&str>) -> String {
+    match profile {
+        Some(name) => format!("AOC_SESSION_{}", name.to_uppercase().replace('-', "_")),
+        None => "AOC_SESSION".to_string(),
+    }
+}
+
+/// Cache directory of a profile, for files that differ between users such as inputs, puzzle pages
+/// and answers. The default profile uses the top level of the cache.
+pub fn profile_dir(profile: Option<&str>) -> Result<PathBuf, Error> {
+    let cache = get_cache()?;
+
+    let dir = match profile {
+        Some(name) => cache.join(PROFILES_DIR).join(name),
+        None => return Ok(cache.clone()),
+    };
+
+    fs::create_dir_all(&dir).map_err(Error::cache(&dir))?;
+    Ok(dir)
+}
+
+/// Cache directory of the selected profile.
+pub(crate) fn profile_cache() -> Result<PathBuf, Error> {
+    profile_dir(config()?.profile.as_deref())
+}
+
+/// Names of every profile with a cache directory.
+pub fn profiles() -> Result<Vec<String>, Error> {
+    let dir = get_cache()?.join(PROFILES_DIR);
+
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut profiles = fs::read_dir(&dir)
+        .map_err(Error::cache(&dir))?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            entry.file_type().ok()?.is_dir().then_some(())?;
+            entry.file_name().into_string().ok()
+        })
+        .collect::<Vec<_>>();
+
+    profiles.sort_unstable();
+    Ok(profiles)
+}
+
+/// Cached inputs of a puzzle for the default profile and every named profile.
+pub fn cached_inputs(year: u16, day: u8) -> Result<Vec<(Option<String>, PathBuf)>, Error> {
+    let file = format!("input-{year}-{day:02}.txt");
+
+    let mut inputs = vec![(None, get_cache()?.join(&file))];
+
+    for profile in profiles()? {
+        let path = profile_dir(Some(&profile))?.join(&file);
+        inputs.push((Some(profile), path));
+    }
+
+    inputs.retain(|(_, path)| path.is_file());
+    Ok(inputs)
+}
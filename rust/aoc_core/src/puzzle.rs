@@ -0,0 +1,147 @@
+use std::{fs, path::PathBuf};
+
+use crate::{Error, check_unlocked, config, download_file, profile_cache};
+
+pub fn delete_cached_puzzle(year: u16, day: u8) -> Result<(), Error> {
+    let path = profile_cache()?.join(format!("puzzle-{year}-{day:02}.html"));
+
+    if path.is_file() {
+        fs::remove_file(&path).map_err(Error::cache(&path))?;
+    }
+
+    Ok(())
+}
+
+pub fn is_puzzle_cached(year: u16, day: u8) -> Result<bool, Error> {
+    Ok(profile_cache()?
+        .join(format!("puzzle-{year}-{day:02}.html"))
+        .is_file())
+}
+
+pub fn load_puzzle_page(year: u16, day: u8) -> Result<String, Error> {
+    let path = profile_cache()?.join(format!("puzzle-{year}-{day:02}.html"));
+
+    if !path.is_file() {
+        check_unlocked(year, day)?;
+        let page =
+            download_file(&format!("{}/{year}/day/{day}", config()?.url)).map_err(Error::locked)?;
+        fs::write(&path, &page).map_err(Error::cache(&path))?;
+        Ok(page)
+    } else {
+        fs::read_to_string(&path).map_err(Error::cache(&path))
+    }
+}
+
+/// Extracts the contents of every `<pre><code>` block in a puzzle page, in page order.
+pub fn extract_examples(html: &str) -> Vec<String> {
+    html.split("<pre><code>")
+        .skip(1)
+        .filter_map(|block| block.split_once("</code></pre>"))
+        .map(|(code, _)| decode_entities(&strip_tags(code)))
+        .collect()
+}
+
+/// Extracts the puzzle title from the `--- Day N: Title ---` heading of a puzzle page.
+pub fn extract_title(html: &str) -> Option<String> {
+    let (_, heading) = html.split_once("<h2>")?;
+    let (heading, _) = heading.split_once("</h2>")?;
+    let heading = decode_entities(heading);
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end_matches('-').trim().to_string())
+}
+
+/// Guesses the answer to the example of each part available on a puzzle page, as the last
+/// emphasised code in the description of the part.
+pub fn extract_example_answers(html: &str) -> [Option<String>; 2] {
+    let mut answers = html
+        .split("<article class=\"day-desc\">")
+        .skip(1)
+        .map(|article| {
+            let article = article.split("</article>").next().unwrap_or_default();
+            let (_, code) = article.rsplit_once("<code><em>")?;
+            let (answer, _) = code.split_once("</em></code>")?;
+            Some(decode_entities(&strip_tags(answer)))
+        });
+
+    [answers.next().flatten(), answers.next().flatten()]
+}
+
+/// Downloads the puzzle page and saves each example block to the cache, returning the examples
+/// along with the paths they were saved to.
+pub fn save_examples(year: u16, day: u8) -> Result<Vec<(PathBuf, String)>, Error> {
+    let examples = extract_examples(&load_puzzle_page(year, day)?);
+
+    examples
+        .into_iter()
+        .enumerate()
+        .map(|(i, example)| {
+            let path = profile_cache()?.join(format!("example-{year}-{day:02}-{}.txt", i + 1));
+            fs::write(&path, &example).map_err(Error::cache(&path))?;
+            Ok((path, example))
+        })
+        .collect()
+}
+
+pub(crate) fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+pub(crate) fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<article class="day-desc"><h2>--- Day 1: Test ---</h2>
+<p>For example:</p>
+<pre><code>L68
+L30
+</code></pre>
+<p>Then <code>x</code> happens:</p>
+<pre><code>&lt;<em>#</em>&gt; &amp; "a"
+..#
+</code></pre>
+</article>"#;
+
+    #[test]
+    fn title_and_answers() {
+        let page = format!(
+            "{PAGE}<p>Your puzzle answer was <code>3</code>.</p>\
+            <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+            <p>Totals <code>1</code> and <code><em>42</em></code>.</p></article>"
+        );
+
+        assert_eq!(extract_title(&page), Some("Test".to_string()));
+        assert_eq!(
+            extract_example_answers(&page),
+            [None, Some("42".to_string())]
+        );
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(
+            extract_examples(PAGE),
+            vec!["L68\nL30\n".to_string(), "<#> & \"a\"\n..#\n".to_string()]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+use colored::Colorize;
+
+use crate::{decode_entities, load_puzzle_page};
+
+const WIDTH: usize = 100;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Heading,
+    Emphasis,
+    Star,
+    Code,
+    Link,
+}
+
+/// Minimal renderer for the subset of HTML used in puzzle descriptions.
+#[derive(Default)]
+struct Renderer {
+    out: String,
+    column: usize,
+    indent: usize,
+    lists: usize,
+    pre: bool,
+    space: bool,
+    styles: Vec<Style>,
+}
+
+impl Renderer {
+    fn break_line(&mut self) {
+        if self.column > 0 {
+            self.out.push('\n');
+            self.column = 0;
+        }
+    }
+
+    fn blank_line(&mut self) {
+        self.break_line();
+
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+
+        self.space = false;
+    }
+
+    fn styled(&self, text: &str) -> String {
+        let mut s = text.normal();
+
+        for style in &self.styles {
+            s = match style {
+                Style::Heading => s.bold().bright_green(),
+                Style::Emphasis => s.bold().bright_white(),
+                Style::Star => s.bold().bright_yellow(),
+                Style::Code if !self.pre => s.bright_cyan(),
+                Style::Code => s,
+                Style::Link => s.underline(),
+            };
+        }
+
+        s.to_string()
+    }
+
+    fn pad(&mut self) {
+        if self.column == 0 && self.indent > 0 {
+            self.out.push_str(&" ".repeat(self.indent));
+            self.column = self.indent;
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+
+        if self.pre {
+            for piece in text.split_inclusive('\n') {
+                let line = piece.trim_end_matches('\n');
+
+                if self.column == 0 && !line.is_empty() {
+                    self.out.push_str("    ");
+                    self.column = 4;
+                }
+
+                let styled = self.styled(line);
+                self.out.push_str(&styled);
+                self.column += line.chars().count();
+
+                if piece.ends_with('\n') {
+                    self.out.push('\n');
+                    self.column = 0;
+                }
+            }
+
+            return;
+        }
+
+        let leading = text.starts_with(char::is_whitespace);
+        let trailing = text.ends_with(char::is_whitespace);
+
+        for (i, word) in text.split_whitespace().enumerate() {
+            let width = word.chars().count();
+            let space = (i > 0 || leading || self.space) && self.column > self.indent;
+
+            if self.column + width + usize::from(space) > WIDTH && self.column > self.indent {
+                self.break_line();
+            } else if space {
+                self.out.push(' ');
+                self.column += 1;
+            }
+
+            self.pad();
+            let styled = self.styled(word);
+            self.out.push_str(&styled);
+            self.column += width;
+        }
+
+        self.space = if text.trim().is_empty() {
+            self.space || !text.is_empty()
+        } else {
+            trailing
+        };
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let closing = tag.starts_with('/');
+        let tag = tag.trim_start_matches('/').trim_end_matches('/');
+        let (name, attributes) = tag.split_once(' ').unwrap_or((tag, ""));
+
+        match (name, closing) {
+            ("h2", false) => {
+                self.blank_line();
+                self.styles.push(Style::Heading);
+            }
+            ("p" | "pre" | "ul" | "h2", true) => {
+                self.styles.retain(|&s| s != Style::Heading);
+
+                if name == "pre" {
+                    self.pre = false;
+                }
+
+                if name == "ul" {
+                    self.lists = self.lists.saturating_sub(1);
+                    self.indent = self.lists * 2;
+                }
+
+                self.blank_line();
+            }
+            ("p", false) => self.blank_line(),
+            ("pre", false) => {
+                self.blank_line();
+                self.pre = true;
+            }
+            ("ul", false) => {
+                self.blank_line();
+                self.lists += 1;
+            }
+            ("li", false) => {
+                self.break_line();
+                self.indent = self.lists.saturating_sub(1) * 2;
+                self.pad();
+                self.out.push_str("- ");
+                self.column += 2;
+                self.indent += 2;
+                self.space = false;
+            }
+            ("li", true) => self.break_line(),
+            ("br", _) => self.break_line(),
+            ("em", false) if attributes.contains("star") => self.styles.push(Style::Star),
+            ("em", false) => self.styles.push(Style::Emphasis),
+            ("code", false) => self.styles.push(Style::Code),
+            ("a", false) => self.styles.push(Style::Link),
+            ("em" | "code" | "a", true) => {
+                self.styles.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Renders the article sections of a puzzle page as terminal text.
+pub fn render_puzzle(html: &str) -> String {
+    let mut renderer = Renderer::default();
+
+    for article in html.split("<article").skip(1) {
+        let article = article
+            .split_once('>')
+            .map_or("", |(_, rest)| rest)
+            .split("</article>")
+            .next()
+            .unwrap_or_default();
+
+        let mut rest = article;
+
+        while let Some((text, after)) = rest.split_once('<') {
+            renderer.text(text);
+
+            let Some((tag, after)) = after.split_once('>') else {
+                break;
+            };
+
+            renderer.tag(tag);
+            rest = after;
+        }
+
+        renderer.text(rest);
+        renderer.blank_line();
+    }
+
+    renderer.out.trim_end().to_string()
+}
+
+pub fn read_puzzle(year: u16, day: u8) -> crate::Anyhow<String> {
+    Ok(render_puzzle(&load_puzzle_page(year, day)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>The <em>first</em> step is <code>x</code>.</p>
+<ul>
+<li>One &amp; <em>two</em>.</li>
+<li>Three.</li>
+</ul>
+<pre><code>ab
+<em>c</em>d
+</code></pre>
+<p>You have <em class="star">one star</em>.</p>
+</article><p>Your puzzle answer was <code>3</code>.</p></main>"#;
+
+    #[test]
+    fn render() {
+        colored::control::set_override(false);
+
+        assert_eq!(
+            render_puzzle(PAGE),
+            "--- Day 1: Test ---\n\nThe first step is x.\n\n- One & two.\n- Three.\n\n    ab\n    cd\n\nYou have one star."
+        );
+    }
+}
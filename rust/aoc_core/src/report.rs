@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Anyhow;
+
+/// Machine-readable results of a solution run, emitted by `solution!` with `--json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub build: Timing,
+    pub parts: Vec<PartReport>,
+    pub total_ns: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: String,
+    #[serde(flatten)]
+    pub timing: Timing,
+}
+
+/// Summary statistics of the sampled durations, with warm-up iterations discarded.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Timing {
+    /// Median duration
+    pub duration_ns: u64,
+    /// Total number of iterations, including warm-up
+    pub iterations: usize,
+    #[serde(default)]
+    pub mean_ns: u64,
+    #[serde(default)]
+    pub min_ns: u64,
+    #[serde(default)]
+    pub std_dev_ns: u64,
+    /// Number of samples the statistics are computed from
+    #[serde(default)]
+    pub samples: usize,
+    /// Number of samples outside the Tukey fences (1.5 IQR beyond the quartiles)
+    #[serde(default)]
+    pub outliers: usize,
+}
+
+impl Timing {
+    pub fn from_samples(durations: &[Duration], warmup: usize) -> Self {
+        if durations.is_empty() {
+            return Self::default();
+        }
+
+        let warmup = warmup.min(durations.len() - 1);
+
+        let mut samples = durations[warmup..]
+            .iter()
+            .map(|d| d.as_nanos() as f64)
+            .collect::<Vec<_>>();
+
+        samples.sort_unstable_by(f64::total_cmp);
+
+        let n = samples.len();
+
+        let quantile = |q: f64| {
+            let position = q * (n - 1) as f64;
+            let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+            samples[lower] + (samples[upper] - samples[lower]) * (position - lower as f64)
+        };
+
+        let mean = samples.iter().sum::<f64>() / n as f64;
+
+        let variance = if n > 1 {
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+
+        let (q1, q3) = (quantile(0.25), quantile(0.75));
+        let fence = 1.5 * (q3 - q1);
+
+        let outliers = samples
+            .iter()
+            .filter(|&&x| x < q1 - fence || x > q3 + fence)
+            .count();
+
+        Self {
+            duration_ns: quantile(0.5).round() as u64,
+            iterations: durations.len(),
+            mean_ns: mean.round() as u64,
+            min_ns: samples[0] as u64,
+            std_dev_ns: variance.sqrt().round() as u64,
+            samples: n,
+            outliers,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.duration_ns)
+    }
+
+    /// Formats the median and spread, e.g.
+    /// `1.349 µs ± 52 ns, min 1.2 µs, mean 1.362 µs, 5000 samples, 12 outliers`, where the
+    /// outliers are only mentioned if there are any.
+    pub fn summary(&self) -> Anyhow<String> {
+        let mut summary = format!(
+            "{} ± {}, min {}, mean {}, {} samples",
+            format_time(self.duration())?,
+            format_time(Duration::from_nanos(self.std_dev_ns))?,
+            format_time(Duration::from_nanos(self.min_ns))?,
+            format_time(Duration::from_nanos(self.mean_ns))?,
+            self.samples,
+        );
+
+        if self.outliers > 0 {
+            summary.push_str(&format!(", {} outliers", self.outliers));
+        }
+
+        Ok(summary)
+    }
+}
+
+impl Report {
+    pub fn to_json(&self) -> Anyhow<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Anyhow<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Durations of the build, part 1, part 2 and total, in that order.
+    pub fn durations(&self) -> [Option<Duration>; 4] {
+        let part = |part| {
+            self.parts
+                .iter()
+                .find(|p| p.part == part)
+                .map(|p| p.timing.duration())
+        };
+
+        [
+            Some(self.build.duration()),
+            part(1),
+            part(2),
+            Some(Duration::from_nanos(self.total_ns)),
+        ]
+    }
+
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.parts
+            .iter()
+            .find(|p| p.part == part)
+            .map(|p| p.answer.as_str())
+    }
+}
+
+/// Result of one solution in a run of many, emitted by the solutions binary with `--json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report: Option<Report>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DayReport {
+    pub fn to_json(&self) -> Anyhow<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Anyhow<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+pub fn format_time(time: Duration) -> Anyhow<String> {
+    if time.is_zero() {
+        return Ok("0 ns".to_string());
+    }
+
+    let s = format!("{time:#?}");
+
+    let number = s
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || c == &'.')
+        .collect::<String>()
+        .parse::<f64>()?;
+
+    let shift = 10_f64.powi(4 - number.abs().log10().ceil() as i32);
+    let rounded = (number * shift).round() / shift;
+    let unit = s.chars().filter(|c| c.is_alphabetic()).collect::<String>();
+    Ok(format!("{rounded} {unit}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn warmup_discarded() {
+        let timing = Timing::from_samples(&nanos(&[1000, 900, 10, 20, 30]), 2);
+        assert_eq!(timing.iterations, 5);
+        assert_eq!(timing.samples, 3);
+        assert_eq!(timing.duration_ns, 20);
+        assert_eq!(timing.mean_ns, 20);
+        assert_eq!(timing.min_ns, 10);
+        assert_eq!(timing.std_dev_ns, 10);
+    }
+
+    #[test]
+    fn warmup_keeps_last_sample() {
+        let timing = Timing::from_samples(&nanos(&[3000]), 5);
+        assert_eq!(timing.samples, 1);
+        assert_eq!(timing.duration_ns, 3000);
+        assert_eq!(timing.std_dev_ns, 0);
+    }
+
+    #[test]
+    fn outliers() {
+        let timing = Timing::from_samples(&nanos(&[10, 11, 12, 10, 11, 12, 10, 11, 500]), 0);
+        assert_eq!(timing.duration_ns, 11);
+        assert_eq!(timing.outliers, 1);
+    }
+}
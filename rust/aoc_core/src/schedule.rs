@@ -0,0 +1,126 @@
+use std::ops::RangeInclusive;
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+
+use crate::Error;
+
+/// Year of the first Advent of Code.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// Number of puzzles in a year, as the calendar was shortened to 12 days from 2025.
+pub fn days_in_year(year: u16) -> u8 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+pub fn puzzle_exists(year: u16, day: u8) -> bool {
+    year >= FIRST_YEAR && (1..=days_in_year(year)).contains(&day)
+}
+
+/// Time a puzzle unlocks, at midnight US Eastern (UTC-5) on its day in December.
+pub fn unlock_time(year: u16, day: u8) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year.into(), 12, day.into(), 5, 0, 0)
+        .single()
+        .unwrap_or_default()
+}
+
+/// Checks that a puzzle exists and has unlocked, so that no requests are made for it otherwise.
+pub fn check_unlocked(year: u16, day: u8) -> Result<(), Error> {
+    if !puzzle_exists(year, day) {
+        return Err(Error::NoSuchPuzzle { year, day });
+    }
+
+    let unlock = unlock_time(year, day);
+
+    if Utc::now() < unlock {
+        return Err(Error::NotUnlocked { year, day, unlock });
+    }
+
+    Ok(())
+}
+
+/// Finds the next puzzle to unlock after the given time.
+pub fn next_unlock(now: DateTime<Utc>) -> (u16, u8, DateTime<Utc>) {
+    let year = now.year() as u16;
+
+    (1..=days_in_year(year))
+        .map(|day| (year, day, unlock_time(year, day)))
+        .find(|&(_, _, unlock)| unlock > now)
+        .unwrap_or_else(|| (year + 1, 1, unlock_time(year + 1, 1)))
+}
+
+/// Most recent year with puzzles unlocked at the given time.
+pub fn latest_event(now: DateTime<Utc>) -> u16 {
+    let year = now.year() as u16;
+
+    if now >= unlock_time(year, 1) {
+        year
+    } else {
+        year - 1
+    }
+}
+
+/// Parses a single day or an inclusive range of days, as `N`, `A..=B`, `A..B` or `A-B`.
+pub fn parse_days(value: &str) -> Result<RangeInclusive<u8>, String> {
+    let parse = |day: &str| {
+        day.trim()
+            .parse::<u8>()
+            .map_err(|e| format!("invalid day {day:?}: {e}"))
+    };
+
+    let days = if let Some((start, end)) = value.split_once("..=") {
+        parse(start)?..=parse(end)?
+    } else if let Some((start, end)) = value.split_once("..") {
+        parse(start)?..=parse(end)?.saturating_sub(1)
+    } else if let Some((start, end)) = value.split_once('-') {
+        parse(start)?..=parse(end)?
+    } else {
+        let day = parse(value)?;
+        day..=day
+    };
+
+    if days.is_empty() || *days.start() == 0 || *days.end() > 25 {
+        Err(format!("{value:?} is not a range of days within 1..=25"))
+    } else {
+        Ok(days)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn schedule() {
+        assert!(puzzle_exists(2024, 25));
+        assert!(!puzzle_exists(2025, 13));
+        assert_eq!(
+            unlock_time(2024, 1).to_rfc3339(),
+            "2024-12-01T05:00:00+00:00"
+        );
+
+        let before = Utc.with_ymd_and_hms(2025, 11, 30, 12, 0, 0).unwrap();
+        assert_eq!(next_unlock(before), (2025, 1, unlock_time(2025, 1)));
+        assert_eq!(latest_event(before), 2024);
+
+        let during = Utc.with_ymd_and_hms(2025, 12, 5, 4, 59, 59).unwrap();
+        assert_eq!(next_unlock(during), (2025, 5, unlock_time(2025, 5)));
+
+        let after = Utc.with_ymd_and_hms(2025, 12, 12, 5, 0, 0).unwrap();
+        assert_eq!(next_unlock(after), (2026, 1, unlock_time(2026, 1)));
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(parse_days("5"), Ok(5..=5));
+        assert_eq!(parse_days("1..=12"), Ok(1..=12));
+        assert_eq!(parse_days("1..13"), Ok(1..=12));
+        assert_eq!(parse_days("1-12"), Ok(1..=12));
+        assert_eq!(parse_days(" 3 - 4 "), Ok(3..=4));
+
+        for invalid in [
+            "12-1", "5..5", "1..1", "0", "0..=3", "26", "20..=26", "x", "-3", "1..=",
+        ] {
+            assert!(parse_days(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::{collections::BTreeSet, fmt};
+
+/// Shape of a puzzle input, used to pick the types and capacities of a solution.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct InputStats {
+    pub lines: usize,
+    pub min_width: usize,
+    pub max_width: usize,
+    pub chars: BTreeSet<char>,
+    /// Groups of lines separated by blank lines
+    pub sections: usize,
+    pub integers: usize,
+    pub max_integers_per_line: usize,
+    pub min_integer: Option<i128>,
+    pub max_integer: Option<i128>,
+}
+
+impl InputStats {
+    pub fn new(input: &str) -> Self {
+        let mut stats = Self::default();
+        let mut in_section = false;
+
+        for line in input.lines() {
+            stats.lines += 1;
+
+            if line.is_empty() {
+                in_section = false;
+            } else if !in_section {
+                in_section = true;
+                stats.sections += 1;
+            }
+
+            let width = line.chars().count();
+            stats.min_width = if stats.lines == 1 {
+                width
+            } else {
+                stats.min_width.min(width)
+            };
+            stats.max_width = stats.max_width.max(width);
+            stats.chars.extend(line.chars());
+
+            let integers = integers(line);
+            stats.integers += integers.len();
+            stats.max_integers_per_line = stats.max_integers_per_line.max(integers.len());
+
+            for n in integers {
+                stats.min_integer = Some(stats.min_integer.map_or(n, |min| min.min(n)));
+                stats.max_integer = Some(stats.max_integer.map_or(n, |max| max.max(n)));
+            }
+        }
+
+        stats
+    }
+
+    pub fn uniform_width(&self) -> bool {
+        self.min_width == self.max_width
+    }
+
+    pub fn negative(&self) -> bool {
+        self.min_integer.is_some_and(|min| min < 0)
+    }
+
+    /// Whether the input looks like a character grid rather than a list of records, which are
+    /// told apart by their separators.
+    pub fn grid(&self) -> bool {
+        self.lines > 1
+            && self.sections == 1
+            && self.uniform_width()
+            && self.max_width > 1
+            && !self.chars.iter().any(|c| matches!(c, ' ' | ',' | ':'))
+    }
+
+    /// Type definitions for a solution, sized from the input.
+    pub fn suggestions(&self) -> Vec<String> {
+        let mut suggestions = Vec::new();
+
+        if self.grid() {
+            let size = self.lines.max(self.max_width) as i128;
+            suggestions.push(format!(
+                "type Coordinate = {}; // grid of {} x {}",
+                integer_type(0, size),
+                self.max_width,
+                self.lines
+            ));
+            suggestions.push(format!(
+                "type Grid<T> = [[T; {}]; {}];",
+                self.max_width, self.lines
+            ));
+        }
+
+        // the digits of a grid are cells rather than integers
+        if let Some((min, max)) = self.min_integer.zip(self.max_integer)
+            && !self.grid()
+        {
+            suggestions.push(format!(
+                "type Number = {}; // integers from {min} to {max}",
+                integer_type(min, max)
+            ));
+
+            if self.max_integers_per_line > 1 {
+                suggestions.push(format!(
+                    "type NumbersVec = MicroVec<Number, {}, u8>; // integers on a line",
+                    self.max_integers_per_line
+                ));
+            }
+        }
+
+        if !self.grid() && self.sections == 1 {
+            suggestions.push(format!("const LINES: usize = {};", self.lines));
+            suggestions.push(format!(
+                "type LinesVec<T> = MicroVec<T, LINES, {}>;",
+                integer_type(0, self.lines as i128)
+            ));
+        }
+
+        suggestions
+    }
+}
+
+impl fmt::Display for InputStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = if self.uniform_width() {
+            format!("{} (uniform)", self.max_width)
+        } else {
+            format!("{} to {}", self.min_width, self.max_width)
+        };
+
+        writeln!(f, "Lines:       {}", self.lines)?;
+        writeln!(f, "Width:       {width}")?;
+        writeln!(f, "Sections:    {}", self.sections)?;
+        writeln!(
+            f,
+            "Characters:  {}",
+            self.chars
+                .iter()
+                .map(|c| format!("{c:?}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )?;
+
+        match self.min_integer.zip(self.max_integer) {
+            Some((min, max)) => writeln!(
+                f,
+                "Integers:    {} from {min} to {max}, at most {} on a line{}",
+                self.integers,
+                self.max_integers_per_line,
+                if self.negative() {
+                    ", some negative"
+                } else {
+                    ""
+                }
+            ),
+            None => writeln!(f, "Integers:    none"),
+        }
+    }
+}
+
+/// Integers in a line. A `-` is read as a sign unless it follows a letter or digit, so ranges
+/// such as `1-3` are two positive integers. Digit runs too long for an integer are skipped.
+fn integers(line: &str) -> Vec<i128> {
+    let bytes = line.as_bytes();
+    let mut integers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let negative = start > 0
+            && bytes[start - 1] == b'-'
+            && (start == 1 || !bytes[start - 2].is_ascii_alphanumeric());
+
+        if let Ok(n) = line[start..i].parse::<i128>() {
+            integers.push(if negative { -n } else { n });
+        }
+    }
+
+    integers
+}
+
+/// Smallest primitive integer type holding every value from `min` to `max`.
+fn integer_type(min: i128, max: i128) -> &'static str {
+    [
+        (u8::MIN as i128, u8::MAX as i128, "u8"),
+        (u16::MIN as i128, u16::MAX as i128, "u16"),
+        (u32::MIN as i128, u32::MAX as i128, "u32"),
+        (u64::MIN as i128, u64::MAX as i128, "u64"),
+        (i8::MIN as i128, i8::MAX as i128, "i8"),
+        (i16::MIN as i128, i16::MAX as i128, "i16"),
+        (i32::MIN as i128, i32::MAX as i128, "i32"),
+        (i64::MIN as i128, i64::MAX as i128, "i64"),
+    ]
+    .into_iter()
+    .find(|&(low, high, _)| low <= min && max <= high)
+    .map_or("i128", |(_, _, name)| name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid() {
+        let stats = InputStats::new("#.S\n..#\nE.#\n");
+
+        assert_eq!(stats.lines, 3);
+        assert!(stats.grid());
+        assert_eq!(stats.chars, BTreeSet::from(['#', '.', 'E', 'S']));
+        assert_eq!(stats.min_integer, None);
+        assert_eq!(
+            stats.suggestions(),
+            [
+                "type Coordinate = u8; // grid of 3 x 3",
+                "type Grid<T> = [[T; 3]; 3];"
+            ]
+        );
+    }
+
+    #[test]
+    fn integers() {
+        let stats = InputStats::new(
+            "1-3 a: x=-5\n\n300,70000\n12345678901234567890123456789012345678901\n",
+        );
+
+        assert_eq!(stats.sections, 2);
+        assert!(!stats.uniform_width());
+        assert_eq!(stats.integers, 5);
+        assert_eq!(stats.max_integers_per_line, 3);
+        assert_eq!(
+            (stats.min_integer, stats.max_integer),
+            (Some(-5), Some(70000))
+        );
+        assert!(stats.negative());
+        assert_eq!(
+            stats.suggestions(),
+            [
+                "type Number = i32; // integers from -5 to 70000",
+                "type NumbersVec = MicroVec<Number, 3, u8>; // integers on a line"
+            ]
+        );
+    }
+
+    #[test]
+    fn lines() {
+        let input = "1,2,3\n".repeat(1000);
+        let suggestions = InputStats::new(&input).suggestions();
+
+        assert_eq!(suggestions[0], "type Number = u8; // integers from 1 to 3");
+        assert_eq!(suggestions[2], "const LINES: usize = 1000;");
+        assert_eq!(
+            suggestions[3],
+            "type LinesVec<T> = MicroVec<T, LINES, u16>;"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use crate::{Anyhow, Error, check_unlocked, config, post_form};
+
+/// Outcome of submitting an answer, parsed from the response page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited(Duration),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl Submission {
+    fn parse(page: &str) -> Self {
+        let text = page
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(page, |(article, _)| article);
+
+        if text.contains("That's the right answer") {
+            Self::Correct
+        } else if text.contains("your answer is too high") {
+            Self::TooHigh
+        } else if text.contains("your answer is too low") {
+            Self::TooLow
+        } else if text.contains("That's not the right answer") {
+            Self::Incorrect
+        } else if text.contains("You gave an answer too recently") {
+            Self::RateLimited(Self::parse_wait(text).unwrap_or_default())
+        } else if text.contains("You don't seem to be solving the right level") {
+            Self::AlreadySolved
+        } else {
+            Self::Unknown(text.to_string())
+        }
+    }
+
+    /// Parses the remaining wait from text such as "You have 1m 5s left to wait".
+    fn parse_wait(text: &str) -> Option<Duration> {
+        let (_, rest) = text.split_once("You have ")?;
+        let (wait, _) = rest.split_once(" left to wait")?;
+
+        wait.split_whitespace()
+            .map(|token| {
+                let (number, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let number = number.parse::<u64>().ok()?;
+
+                match unit {
+                    "h" => Some(number * 3600),
+                    "m" => Some(number * 60),
+                    "s" => Some(number),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs)
+    }
+}
+
+pub fn submit_answer(year: u16, day: u8, part: u8, answer: &str) -> Anyhow<Submission> {
+    check_unlocked(year, day)?;
+    let url = format!("{}/{year}/day/{day}/answer", config()?.url);
+    let level = part.to_string();
+    let page = post_form(&url, &[("level", &level), ("answer", answer)]).map_err(Error::locked)?;
+    Ok(Submission::parse(&page))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn correct() {
+        let page = "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>";
+        assert_eq!(Submission::parse(page), Submission::Correct);
+    }
+
+    #[test]
+    fn too_high() {
+        let page =
+            "<article><p>That's not the right answer; your answer is too high.</p></article>";
+        assert_eq!(Submission::parse(page), Submission::TooHigh);
+    }
+
+    #[test]
+    fn too_low() {
+        let page = "<article><p>That's not the right answer; your answer is too low.</p></article>";
+        assert_eq!(Submission::parse(page), Submission::TooLow);
+    }
+
+    #[test]
+    fn incorrect() {
+        let page = "<article><p>That's not the right answer. If you're stuck, make sure you're using the full input data.</p></article>";
+        assert_eq!(Submission::parse(page), Submission::Incorrect);
+    }
+
+    #[test]
+    fn rate_limited() {
+        let page = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>";
+        assert_eq!(
+            Submission::parse(page),
+            Submission::RateLimited(Duration::from_secs(65))
+        );
+    }
+
+    #[test]
+    fn already_solved() {
+        let page = "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>";
+        assert_eq!(Submission::parse(page), Submission::AlreadySolved);
+    }
+}
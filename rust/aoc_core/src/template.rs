@@ -1,43 +1,177 @@
-#[derive(Clone)]
-struct Solution {
-    lines: Vec<String>,
+use std::{fs, path::PathBuf};
+
+use anyhow::anyhow;
+
+use crate::{Anyhow, repository};
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Values substituted for the placeholders of a solution template.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateValues {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    pub example: String,
+    /// Answers to the example of each part, if they were found on the puzzle page
+    pub answers: [Option<String>; 2],
 }
 
-impl Solver for Solution {
-    fn new(input: &str) -> Anyhow<Self> {
-        Ok(Self {
-            lines: input.lines().map(String::from).collect(),
-        })
-    }
+/// Templates built into the runner, so they are available wherever it is installed.
+const BUILT_IN: [(&str, &str); 5] = [
+    ("bytes", include_str!("../templates/bytes.rs")),
+    ("default", include_str!("../templates/default.rs")),
+    ("graph", include_str!("../templates/graph.rs")),
+    ("grid", include_str!("../templates/grid.rs")),
+    ("numbers", include_str!("../templates/numbers.rs")),
+];
+
+/// Directory of the solution templates, each a `<name>.rs` file. Only templates added there that
+/// are not built in are read from it.
+pub fn template_dir() -> PathBuf {
+    repository().join("rust/aoc_core/templates")
+}
+
+/// Names of every solution template, built in or added to the template directory.
+pub fn templates() -> Vec<String> {
+    let added = fs::read_dir(template_dir())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            path.extension()
+                .is_some_and(|ext| ext == "rs")
+                .then_some(())?;
+            Some(path.file_stem()?.to_str()?.to_string())
+        });
 
-    fn part1(&mut self) -> Anyhow<impl fmt::Display> {
-        Ok(true)
+    let mut templates = BUILT_IN
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .chain(added)
+        .collect::<Vec<_>>();
+
+    templates.sort_unstable();
+    templates.dedup();
+    templates
+}
+
+pub fn load_template(name: &str) -> Anyhow<String> {
+    if let Some((_, template)) = BUILT_IN.iter().find(|(built_in, _)| *built_in == name) {
+        return Ok(template.to_string());
     }
 
-    fn part2(&mut self) -> Anyhow<impl fmt::Display> {
-        Ok(true)
+    let path = template_dir().join(format!("{name}.rs"));
+
+    if !path.is_file() {
+        return Err(anyhow!(
+            "no template named {name:?}, choose from {}",
+            templates().join(", ")
+        ));
     }
+
+    Ok(fs::read_to_string(path)?)
 }
 
-aoc::solution!();
+/// Fills in the placeholders of a template. `{{year}}`, `{{day}}` and `{{title}}` are replaced as
+/// they are, `{{example}}` with a raw string literal of the example input, and `{{answer1}}` and
+/// `{{answer2}}` with the example answers escaped for a string literal, or nothing if not found.
+pub fn fill_template(template: &str, values: &TemplateValues) -> String {
+    let answer = |part: usize| {
+        values.answers[part]
+            .as_deref()
+            .map(|answer| answer.escape_default().to_string())
+            .unwrap_or_default()
+    };
+
+    template
+        .replace("{{year}}", &values.year.to_string())
+        .replace("{{day}}", &values.day.to_string())
+        .replace("{{title}}", &values.title)
+        .replace("{{answer1}}", &answer(0))
+        .replace("{{answer2}}", &answer(1))
+        .replace("{{example}}", &raw_string_literal(&values.example))
+}
+
+/// Whether a solution has tests with an example input. Templates filled in without an example
+/// leave an empty raw string literal in place of it.
+pub fn has_example_tests(source: &str) -> bool {
+    source.contains("#[test]") && !source.contains(&raw_string_literal(""))
+}
+
+/// Quotes text as a raw string literal, adding enough hashes to allow quotes in the text.
+pub fn raw_string_literal(text: &str) -> String {
+    let hashes = (0..)
+        .map(|n| "#".repeat(n))
+        .find(|hashes| !text.contains(&format!("\"{hashes}")))
+        .unwrap_or_default();
+
+    format!("r{hashes}\"{text}\"{hashes}")
+}
 
 #[cfg(test)]
 mod test {
-    use super::{Solution, Solver};
+    use super::*;
+
+    const TEMPLATE: &str = "// {{year}} day {{day}}: {{title}}
+const INPUT: &str = {{example}};
+const ANSWERS: [&str; 2] = [\"{{answer1}}\", \"{{answer2}}\"];
+";
+
+    #[test]
+    fn placeholders() {
+        let values = TemplateValues {
+            year: 2024,
+            day: 5,
+            title: "Print Queue".to_string(),
+            example: "a\"#b\n".to_string(),
+            answers: [Some("143".to_string()), None],
+        };
 
-    const INPUT: &str = r"";
+        assert_eq!(
+            fill_template(TEMPLATE, &values),
+            "// 2024 day 5: Print Queue
+const INPUT: &str = r##\"a\"#b\n\"##;
+const ANSWERS: [&str; 2] = [\"143\", \"\"];
+"
+        );
+    }
 
     #[test]
-    fn test_part1() {
-        let mut solution = Solution::new(INPUT).unwrap();
-        let answer = solution.part1().unwrap().to_string();
-        assert_eq!(answer, "true");
+    fn templates_parse() {
+        let values = TemplateValues {
+            year: 2024,
+            day: 5,
+            title: "Print Queue".to_string(),
+            example: "47|53\n\"quoted\"#\n".to_string(),
+            answers: [Some("143".to_string()), None],
+        };
+
+        for (name, template) in BUILT_IN {
+            let source = fill_template(template, &values);
+
+            if let Err(e) = syn::parse_file(&source) {
+                panic!("template {name} does not parse once filled in: {e}");
+            }
+
+            assert!(
+                source.starts_with("// 2024 day 5: Print Queue\n"),
+                "template {name} has no header"
+            );
+        }
+
+        assert!(templates().contains(&DEFAULT_TEMPLATE.to_string()));
+        assert!(load_template("missing").is_err());
     }
 
     #[test]
-    fn test_part2() {
-        let mut solution = Solution::new(INPUT).unwrap();
-        let answer = solution.part2().unwrap().to_string();
-        assert_eq!(answer, "true");
+    fn example_tests() {
+        let template = load_template(DEFAULT_TEMPLATE).unwrap();
+        let mut values = TemplateValues::default();
+        assert!(!has_example_tests(&fill_template(&template, &values)));
+
+        values.example = "1 2\n".to_string();
+        assert!(has_example_tests(&fill_template(&template, &values)));
+        assert!(!has_example_tests("fn main() {}"));
     }
 }
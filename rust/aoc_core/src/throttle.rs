@@ -0,0 +1,207 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, Write},
+    path::Path,
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+
+use crate::{Error, config, profile_cache};
+
+const LOG_FILE: &str = "log.txt";
+const RETRY_AFTER_PREFIX: &str = "retry-after ";
+
+/// Times of recent requests and any backoff requested by the server, shared between processes
+/// through the log file in the cache.
+#[derive(Debug, Default, PartialEq)]
+struct RequestLog {
+    requests: Vec<DateTime<Utc>>,
+    retry_after: Option<DateTime<Utc>>,
+}
+
+impl RequestLog {
+    /// Parses the log, skipping malformed lines rather than failing every request because of them.
+    fn parse(content: &str) -> Self {
+        let mut log = Self::default();
+
+        for line in content.lines().map(str::trim) {
+            let (timestamp, retry) = match line.strip_prefix(RETRY_AFTER_PREFIX) {
+                Some(timestamp) => (timestamp, true),
+                None => (line, false),
+            };
+
+            let Ok(timestamp) = DateTime::parse_from_rfc3339(timestamp) else {
+                continue;
+            };
+
+            let timestamp = timestamp.with_timezone(&Utc);
+
+            if retry {
+                log.retry_after = log.retry_after.max(Some(timestamp));
+            } else {
+                log.requests.push(timestamp);
+            }
+        }
+
+        log.requests.sort_unstable();
+        log
+    }
+
+    /// Time to wait before the next request, if any.
+    fn wait(&self, now: DateTime<Utc>, max_requests: usize, period: Duration) -> Option<Duration> {
+        let period = chrono::Duration::from_std(period).ok()?;
+
+        let recent = self
+            .requests
+            .iter()
+            .filter(|&&t| now.signed_duration_since(t) < period)
+            .collect::<Vec<_>>();
+
+        let throttled =
+            (recent.len() >= max_requests).then(|| *recent[recent.len() - max_requests] + period);
+
+        let until = throttled.max(self.retry_after)?;
+        (until - now).to_std().ok().filter(|wait| !wait.is_zero())
+    }
+
+    /// Formats the log, dropping requests and backoff that no longer affect throttling.
+    fn render(&self, now: DateTime<Utc>, period: Duration) -> String {
+        let period = chrono::Duration::from_std(period).unwrap_or(chrono::Duration::MAX);
+
+        let requests = self
+            .requests
+            .iter()
+            .filter(|&&t| now.signed_duration_since(t) < period)
+            .map(|t| t.to_rfc3339());
+
+        let retry_after = self
+            .retry_after
+            .filter(|&t| t > now)
+            .map(|t| format!("{RETRY_AFTER_PREFIX}{}", t.to_rfc3339()));
+
+        requests.chain(retry_after).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Opens the request log with an exclusive lock, which is held until the file is dropped so that
+/// concurrent processes read and update the log one at a time.
+fn lock_log(path: &Path) -> Result<(File, RequestLog), Error> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(Error::cache(path))?;
+
+    file.lock().map_err(Error::cache(path))?;
+
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(Error::cache(path))?;
+
+    Ok((file, RequestLog::parse(&content)))
+}
+
+fn write_log(path: &Path, mut file: File, log: &RequestLog) -> Result<(), Error> {
+    let content = log.render(Utc::now(), config()?.request_period);
+
+    file.set_len(0)
+        .and_then(|_| file.rewind())
+        .and_then(|_| file.write_all(content.as_bytes()))
+        .map_err(Error::cache(path))
+}
+
+/// Waits until a request is allowed and records it. The log stays locked while waiting, so other
+/// processes queue behind this request instead of all sending at once.
+pub(crate) fn throttle_requests() -> Result<(), Error> {
+    let config = config()?;
+    let path = profile_cache()?.join(LOG_FILE);
+    let (file, mut log) = lock_log(&path)?;
+
+    if let Some(wait) = log.wait(Utc::now(), config.max_requests, config.request_period) {
+        println!("Request throttled, waiting {wait:#?} before sending the request.");
+        thread::sleep(wait);
+    }
+
+    log.requests.push(Utc::now());
+    write_log(&path, file, &log)
+}
+
+/// Time the next request would currently wait for, without recording a request.
+pub fn request_wait() -> Result<Option<Duration>, Error> {
+    let config = config()?;
+    let (_, log) = lock_log(&profile_cache()?.join(LOG_FILE))?;
+    Ok(log.wait(Utc::now(), config.max_requests, config.request_period))
+}
+
+/// Records a backoff requested by the server, so that every process waits it out.
+pub(crate) fn record_retry_after(wait: Duration) -> Result<(), Error> {
+    let path = profile_cache()?.join(LOG_FILE);
+    let (file, mut log) = lock_log(&path)?;
+
+    let until = Utc::now() + chrono::Duration::from_std(wait).unwrap_or(chrono::Duration::MAX);
+    log.retry_after = log.retry_after.max(Some(until));
+    write_log(&path, file, &log)
+}
+
+/// Parses a `Retry-After` header, given either in seconds or as an HTTP date.
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    value.parse().map(Duration::from_secs).ok().or_else(|| {
+        let date = DateTime::parse_from_rfc2822(value).ok()?;
+        date.with_timezone(&Utc)
+            .signed_duration_since(Utc::now())
+            .to_std()
+            .ok()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LOG: &str = "2024-12-01T05:00:00+00:00
+not a timestamp
+2024-12-01T05:01:00+00:00
+retry-after 2024-12-01T05:20:00+00:00
+2024-12-01T05:02:00+00:00";
+
+    fn time(minute: u32) -> DateTime<Utc> {
+        use chrono::TimeZone;
+        Utc.with_ymd_and_hms(2024, 12, 1, 5, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn request_log() {
+        let minutes = |n: u64| Duration::from_secs(n * 60);
+        let log = RequestLog::parse(LOG);
+
+        assert_eq!(log.requests, vec![time(0), time(1), time(2)]);
+        assert_eq!(log.retry_after, Some(time(20)));
+        assert_eq!(log.wait(time(10), 3, minutes(15)), Some(minutes(10)));
+
+        let log = RequestLog {
+            retry_after: None,
+            ..log
+        };
+
+        assert_eq!(log.wait(time(10), 3, minutes(15)), Some(minutes(5)));
+        assert_eq!(log.wait(time(10), 4, minutes(15)), None);
+
+        assert_eq!(
+            log.render(time(15), minutes(15)),
+            "2024-12-01T05:01:00+00:00\n2024-12-01T05:02:00+00:00"
+        );
+    }
+
+    #[test]
+    fn retry_after() {
+        assert_eq!(parse_retry_after(" 120 "), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
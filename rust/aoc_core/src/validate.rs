@@ -0,0 +1,74 @@
+use std::{fs, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::{Error, profile_cache, unlock_time};
+
+const QUARANTINE_DIR: &str = "quarantine";
+
+/// Text from server responses that never appears in a real puzzle input.
+const ERROR_MARKERS: [&str; 5] = [
+    "Please log in",
+    "Puzzle inputs differ by user",
+    "before it unlocks",
+    "<!DOCTYPE",
+    "<html",
+];
+
+/// Checks that a response looks like a real puzzle input, returning the reason it is rejected.
+pub fn check_input(year: u16, day: u8, input: &str, fetched: DateTime<Utc>) -> Result<(), String> {
+    let unlock = unlock_time(year, day);
+
+    if fetched < unlock {
+        return Err(format!(
+            "it was fetched before the puzzle unlocked at {unlock}"
+        ));
+    }
+
+    if input.trim().is_empty() {
+        return Err("it is empty".to_string());
+    }
+
+    if let Some(marker) = ERROR_MARKERS.iter().find(|marker| input.contains(*marker)) {
+        return Err(format!(
+            "it contains {marker:?} and looks like an error page"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Saves a rejected input outside of the input cache, so it can be inspected but is never loaded.
+pub(crate) fn quarantine_input(year: u16, day: u8, input: &str) -> Result<PathBuf, Error> {
+    let dir = profile_cache()?.join(QUARANTINE_DIR);
+    fs::create_dir_all(&dir).map_err(Error::cache(&dir))?;
+
+    let path = dir.join(format!(
+        "input-{year}-{day:02}-{}.txt",
+        Utc::now().format("%Y%m%dT%H%M%S")
+    ));
+
+    fs::write(&path, input).map_err(Error::cache(&path))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "3   4\n4   3\n";
+    const LOG_IN: &str = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+    const LOCKED: &str = "Please don't repeatedly request this endpoint before it unlocks!\n";
+
+    #[test]
+    fn rejected_inputs() {
+        let unlock = unlock_time(2024, 1);
+        let fetched = unlock + chrono::Duration::hours(1);
+
+        assert!(check_input(2024, 1, INPUT, fetched).is_ok());
+        assert!(check_input(2024, 1, INPUT, unlock - chrono::Duration::seconds(1)).is_err());
+        assert!(check_input(2024, 1, "\n", fetched).is_err());
+        assert!(check_input(2024, 1, LOG_IN, fetched).is_err());
+        assert!(check_input(2024, 1, LOCKED, fetched).is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use std::{collections::BTreeMap, ops::Range, path::PathBuf};
+
+use anyhow::anyhow;
+
+use crate::{Anyhow, repository};
+
+const MEMBERS: &str = "members = [";
+
+/// Rust workspace of the repository, which holds the crate of each year whichever directory the
+/// tool is run from.
+pub fn workspace() -> Anyhow<PathBuf> {
+    let workspace = repository().join("rust");
+    let manifest = workspace.join("Cargo.toml");
+
+    if manifest.is_file() {
+        Ok(workspace)
+    } else {
+        Err(anyhow!(
+            "failed to find the workspace manifest {manifest:?}"
+        ))
+    }
+}
+
+/// Manifest of the crate holding the solutions of a year.
+pub fn year_manifest(year: u16) -> String {
+    format!(
+        r#"[package]
+name = "aoc-{year}"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+aoc = {{ workspace = true }}
+"#
+    )
+}
+
+/// Adds a member to the workspace manifest, keeping the years in order after the other members.
+pub fn add_workspace_member(manifest: &str, member: &str) -> Anyhow<String> {
+    let start = manifest
+        .match_indices(MEMBERS)
+        .map(|(i, _)| i)
+        .find(|&i| i == 0 || manifest[..i].ends_with('\n'))
+        .ok_or_else(|| anyhow!("failed to find the workspace members"))?
+        + MEMBERS.len();
+
+    let end = manifest[start..]
+        .find(']')
+        .map(|end| start + end)
+        .ok_or_else(|| anyhow!("failed to find the end of the workspace members"))?;
+
+    let mut members = manifest[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|member| !member.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let quoted = format!("{member:?}");
+
+    if members.contains(&quoted) {
+        return Ok(manifest.to_string());
+    }
+
+    let year = |member: &str| member.trim_matches('"').parse::<u16>().ok();
+
+    let position = members
+        .iter()
+        .position(|other| year(other).zip(year(member)).is_some_and(|(a, b)| a > b))
+        .unwrap_or(members.len());
+
+    members.insert(position, quoted);
+
+    Ok(format!(
+        "{}{}{}",
+        &manifest[..start],
+        members.join(", "),
+        &manifest[end..]
+    ))
+}
+
+/// Adds an empty section for the solutions of a year to the README, newest year first. The newest
+/// year is the one expanded by default.
+pub fn add_readme_year(readme: &str, year: u16) -> String {
+    let summary = |year: u16| format!("<summary>{year} Solutions</summary>");
+
+    if readme.contains(&summary(year)) {
+        return readme.to_string();
+    }
+
+    let sections = readme
+        .match_indices("<details")
+        .filter_map(|(i, _)| {
+            let rest = &readme[i..];
+            let year = rest
+                .split_once("<summary>")?
+                .1
+                .split_once(" Solutions</summary>")?
+                .0
+                .parse::<u16>()
+                .ok()?;
+            Some((i, year))
+        })
+        .collect::<Vec<_>>();
+
+    let newest = sections.iter().all(|&(_, other)| other < year);
+
+    let position = match sections.iter().find(|&&(_, other)| other < year) {
+        Some(&(i, _)) => i,
+        // after the oldest section, or before the first heading if there are none
+        None => match sections.last() {
+            Some(&(i, _)) => {
+                let end = readme[i..]
+                    .find("</details>")
+                    .map_or(readme.len(), |end| i + end + "</details>".len());
+
+                readme.len() - readme[end..].trim_start().len()
+            }
+            None => readme.find("\n## ").map_or(readme.len(), |i| i + 1),
+        },
+    };
+
+    let section = format!(
+        "<details{}>\n{}\n\n</details>\n\n",
+        if newest { " open" } else { "" },
+        summary(year)
+    );
+
+    let (before, after) = readme.split_at(position);
+
+    let after = if newest {
+        after.replacen("<details open>", "<details>", 1)
+    } else {
+        after.to_string()
+    };
+
+    format!("{before}{section}{after}")
+}
+
+/// Line listing a solution in the README, with its puzzle title and runtime if known.
+pub fn readme_entry(year: u16, day: u8, title: Option<&str>, time: Option<&str>) -> String {
+    let title = title.map(|title| format!(": {title}")).unwrap_or_default();
+    let time = time.map(|time| format!(" ({time})")).unwrap_or_default();
+    format!("* [Day {day}{title}](./{year}/src/bin/day{day:02}.rs){time}")
+}
+
+/// Range of the contents of the README section of a year, between its summary and `</details>`.
+fn readme_section(readme: &str, year: u16) -> Option<Range<usize>> {
+    let summary = format!("<summary>{year} Solutions</summary>");
+    let start = readme.find(&summary)? + summary.len();
+    let end = start + readme[start..].find("</details>")?;
+    Some(start..end)
+}
+
+/// Titles of the solutions listed in the README section of a year.
+pub fn readme_titles(readme: &str, year: u16) -> BTreeMap<u8, String> {
+    let Some(section) = readme_section(readme, year) else {
+        return BTreeMap::new();
+    };
+
+    readme[section]
+        .lines()
+        .filter_map(|line| {
+            let (label, _) = line.strip_prefix("* [Day ")?.split_once("](")?;
+            let (day, title) = label.split_once(": ")?;
+            Some((day.parse().ok()?, title.to_string()))
+        })
+        .collect()
+}
+
+/// Replaces the list of solutions in the README section of a year, adding the section if it is
+/// missing. Sections holding anything other than a list, like the one for 2022, are left as is.
+pub fn set_readme_solutions(readme: &str, year: u16, entries: &[String]) -> String {
+    let readme = add_readme_year(readme, year);
+
+    let Some(section) = readme_section(&readme, year) else {
+        return readme;
+    };
+
+    if !readme[section.clone()]
+        .lines()
+        .all(|line| line.is_empty() || line.starts_with("* [Day "))
+    {
+        return readme;
+    }
+
+    let list = if entries.is_empty() {
+        "\n\n".to_string()
+    } else {
+        format!("\n\n{}\n\n", entries.join("\n"))
+    };
+
+    format!(
+        "{}{list}{}",
+        &readme[..section.start],
+        &readme[section.end..]
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MANIFEST: &str = "[workspace]
+members = [\"aoc_core\", \"aoc\", \"2022\", \"2024\"]
+default-members = [\"aoc_core\"]
+";
+
+    const README: &str = "# Solutions
+
+<details open>
+<summary>2024 Solutions</summary>
+
+* [Day 1](./2024/src/bin/day01.rs)
+
+</details>
+
+<details>
+<summary>2022 Solutions</summary>
+
+* [Day 1](./2022/src/bin/day01.rs)
+
+</details>
+
+## Runner
+";
+
+    #[test]
+    fn workspace_members() {
+        let manifest = add_workspace_member(MANIFEST, "2023").unwrap();
+        assert!(
+            manifest.contains("members = [\"aoc_core\", \"aoc\", \"2022\", \"2023\", \"2024\"]\n")
+        );
+        assert!(manifest.contains("default-members = [\"aoc_core\"]"));
+
+        let manifest = add_workspace_member(&manifest, "2025").unwrap();
+        assert!(manifest.contains("\"2024\", \"2025\"]"));
+        assert_eq!(add_workspace_member(&manifest, "2025").unwrap(), manifest);
+    }
+
+    #[test]
+    fn readme_years() {
+        let readme = add_readme_year(README, 2025);
+        assert!(readme.starts_with("# Solutions\n\n<details open>\n<summary>2025 Solutions</summary>\n\n</details>\n\n<details>\n<summary>2024"));
+
+        let readme = add_readme_year(README, 2023);
+        assert!(readme.contains("</details>\n\n<details>\n<summary>2023 Solutions</summary>\n\n</details>\n\n<details>\n<summary>2022"));
+
+        let readme = add_readme_year(README, 2021);
+        assert!(readme.contains("<summary>2021 Solutions</summary>\n\n</details>\n\n## Runner"));
+        assert_eq!(add_readme_year(README, 2024), README);
+    }
+
+    #[test]
+    fn readme_solutions() {
+        let readme = README.replace("[Day 1]", "[Day 1: Historian Hysteria]");
+        assert_eq!(
+            readme_titles(&readme, 2024),
+            BTreeMap::from([(1, "Historian Hysteria".to_string())])
+        );
+
+        let entries = [
+            readme_entry(2024, 1, Some("Historian Hysteria"), Some("12 µs")),
+            readme_entry(2024, 10, None, None),
+        ];
+
+        let readme = set_readme_solutions(&readme, 2024, &entries);
+        assert!(readme.contains("<summary>2024 Solutions</summary>\n\n* [Day 1: Historian Hysteria](./2024/src/bin/day01.rs) (12 µs)\n* [Day 10](./2024/src/bin/day10.rs)\n\n</details>"));
+
+        let readme = set_readme_solutions(&readme, 2025, &[readme_entry(2025, 1, None, None)]);
+        assert!(readme.contains("<details open>\n<summary>2025 Solutions</summary>\n\n* [Day 1](./2025/src/bin/day01.rs)\n\n</details>"));
+
+        let custom = README.replace("* [Day 1](./2022/src/bin/day01.rs)", "Old solutions");
+        assert_eq!(set_readme_solutions(&custom, 2022, &entries), custom);
+    }
+}
@@ -0,0 +1,67 @@
+// {{year}} day {{day}}: {{title}}
+
+#[derive(Clone)]
+struct Solution {
+    numbers: Vec<u32>,
+}
+
+impl Solver for Solution {
+    fn new(input: &str) -> Anyhow<Self> {
+        let bytes = input.as_bytes();
+        let mut numbers = Vec::with_capacity(1000);
+
+        unsafe {
+            let mut p = bytes.as_ptr();
+            let end = p.add(bytes.len());
+
+            while p < end {
+                if !(*p).is_ascii_digit() {
+                    p = p.add(1);
+                    continue;
+                }
+
+                let mut n = 0;
+
+                while p < end && (*p).is_ascii_digit() {
+                    n = n * 10 + (*p - b'0') as u32;
+                    p = p.add(1);
+                }
+
+                numbers.push(n);
+            }
+        }
+
+        Ok(Self { numbers })
+    }
+
+    fn part1(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(self.numbers.iter().sum::<u32>())
+    }
+
+    fn part2(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(self.numbers.len())
+    }
+}
+
+aoc::solution!();
+
+#[cfg(test)]
+mod test {
+    use super::{Solution, Solver};
+
+    const INPUT: &str = {{example}};
+
+    #[test]
+    fn test_part1() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part1().unwrap().to_string();
+        assert_eq!(answer, "{{answer1}}");
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part2().unwrap().to_string();
+        assert_eq!(answer, "{{answer2}}");
+    }
+}
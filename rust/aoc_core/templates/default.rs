@@ -0,0 +1,45 @@
+// {{year}} day {{day}}: {{title}}
+
+#[derive(Clone)]
+struct Solution {
+    lines: Vec<String>,
+}
+
+impl Solver for Solution {
+    fn new(input: &str) -> Anyhow<Self> {
+        Ok(Self {
+            lines: input.lines().map(String::from).collect(),
+        })
+    }
+
+    fn part1(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(true)
+    }
+
+    fn part2(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(true)
+    }
+}
+
+aoc::solution!();
+
+#[cfg(test)]
+mod test {
+    use super::{Solution, Solver};
+
+    const INPUT: &str = {{example}};
+
+    #[test]
+    fn test_part1() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part1().unwrap().to_string();
+        assert_eq!(answer, "{{answer1}}");
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part2().unwrap().to_string();
+        assert_eq!(answer, "{{answer2}}");
+    }
+}
@@ -0,0 +1,82 @@
+// {{year}} day {{day}}: {{title}}
+
+#[derive(Clone)]
+struct Solution {
+    names: Vec<String>,
+    edges: Vec<Vec<usize>>,
+}
+
+impl Solution {
+    fn reachable(&self, start: usize) -> usize {
+        let mut seen = vec![false; self.names.len()];
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            if !std::mem::replace(&mut seen[node], true) {
+                stack.extend(self.edges[node].iter().copied().filter(|&next| !seen[next]));
+            }
+        }
+
+        seen.into_iter().filter(|&seen| seen).count()
+    }
+}
+
+impl Solver for Solution {
+    fn new(input: &str) -> Anyhow<Self> {
+        let mut ids = FxHashMap::default();
+        let mut names = Vec::new();
+        let mut edges = Vec::<Vec<usize>>::new();
+
+        let mut id = |name: &str| {
+            *ids.entry(name.to_string()).or_insert_with(|| {
+                names.push(name.to_string());
+                edges.push(Vec::new());
+                names.len() - 1
+            })
+        };
+
+        let pairs = input
+            .lines()
+            .filter_map(|line| line.split_once('-'))
+            .map(|(a, b)| (id(a.trim()), id(b.trim())))
+            .collect::<Vec<_>>();
+
+        for (a, b) in pairs {
+            edges[a].push(b);
+            edges[b].push(a);
+        }
+
+        Ok(Self { names, edges })
+    }
+
+    fn part1(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(self.reachable(0))
+    }
+
+    fn part2(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(self.names.len())
+    }
+}
+
+aoc::solution!();
+
+#[cfg(test)]
+mod test {
+    use super::{Solution, Solver};
+
+    const INPUT: &str = {{example}};
+
+    #[test]
+    fn test_part1() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part1().unwrap().to_string();
+        assert_eq!(answer, "{{answer1}}");
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part2().unwrap().to_string();
+        assert_eq!(answer, "{{answer2}}");
+    }
+}
@@ -0,0 +1,68 @@
+// {{year}} day {{day}}: {{title}}
+
+#[derive(Clone)]
+struct Solution {
+    grid: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Solution {
+    fn get(&self, x: usize, y: usize) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.grid[y * self.width + x])
+    }
+
+    fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                let (x, y) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+                self.get(x, y).map(|_| (x, y))
+            })
+    }
+}
+
+impl Solver for Solution {
+    fn new(input: &str) -> Anyhow<Self> {
+        let width = input.lines().next().map_or(0, str::len);
+
+        let grid = input
+            .lines()
+            .flat_map(|line| line.bytes())
+            .collect::<Vec<_>>();
+
+        let height = grid.len() / width.max(1);
+        Ok(Self { grid, width, height })
+    }
+
+    fn part1(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(self.neighbours(0, 0).count())
+    }
+
+    fn part2(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(self.get(0, 0).is_some())
+    }
+}
+
+aoc::solution!();
+
+#[cfg(test)]
+mod test {
+    use super::{Solution, Solver};
+
+    const INPUT: &str = {{example}};
+
+    #[test]
+    fn test_part1() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part1().unwrap().to_string();
+        assert_eq!(answer, "{{answer1}}");
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part2().unwrap().to_string();
+        assert_eq!(answer, "{{answer2}}");
+    }
+}
@@ -0,0 +1,48 @@
+// {{year}} day {{day}}: {{title}}
+
+#[derive(Clone)]
+struct Solution {
+    rows: Vec<Vec<i64>>,
+}
+
+impl Solver for Solution {
+    fn new(input: &str) -> Anyhow<Self> {
+        Ok(Self {
+            rows: input
+                .lines()
+                .map(|line| NumberParserSigned::from(line).collect())
+                .collect(),
+        })
+    }
+
+    fn part1(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(self.rows.iter().flatten().sum::<i64>())
+    }
+
+    fn part2(&mut self) -> Anyhow<impl fmt::Display> {
+        Ok(self.rows.len())
+    }
+}
+
+aoc::solution!();
+
+#[cfg(test)]
+mod test {
+    use super::{Solution, Solver};
+
+    const INPUT: &str = {{example}};
+
+    #[test]
+    fn test_part1() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part1().unwrap().to_string();
+        assert_eq!(answer, "{{answer1}}");
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut solution = Solution::new(INPUT).unwrap();
+        let answer = solution.part2().unwrap().to_string();
+        assert_eq!(answer, "{{answer2}}");
+    }
+}
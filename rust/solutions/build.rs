@@ -0,0 +1,105 @@
+//! Generates the registry of the solutions in the workspace, found as `{year}/src/bin/dayNN.rs`,
+//! by including each one as a module of this crate.
+
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+/// Crate features enabled in `lib.rs`. Solutions using any other feature are left out, since
+/// features such as `generic_const_exprs` change how every other solution is compiled too.
+const FEATURES: [&str; 1] = ["portable_simd"];
+
+/// Features a solution enables with `#![feature(...)]` attributes.
+fn features(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#![feature(")?.strip_suffix(")]"))
+        .flat_map(|features| features.split(','))
+        .map(|feature| feature.trim().to_string())
+        .collect()
+}
+
+fn main() {
+    let manifest = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("missing manifest dir"));
+    let workspace = manifest.parent().expect("missing workspace directory");
+
+    // new years are added to the workspace members
+    println!(
+        "cargo::rerun-if-changed={}",
+        workspace.join("Cargo.toml").display()
+    );
+
+    let mut solutions = Vec::new();
+
+    for entry in fs::read_dir(workspace).expect("failed to read workspace directory") {
+        let path = entry.expect("failed to read workspace directory").path();
+
+        let Some(year) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<u16>().ok())
+        else {
+            continue;
+        };
+
+        let bin = path.join("src/bin");
+
+        if !path.join("Cargo.toml").is_file() || !bin.is_dir() {
+            continue;
+        }
+
+        println!("cargo::rerun-if-changed={}", bin.display());
+
+        for entry in fs::read_dir(&bin).expect("failed to read solution directory") {
+            let path = entry.expect("failed to read solution directory").path();
+
+            let day = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("day")?.strip_suffix(".rs"))
+                .filter(|day| day.len() == 2)
+                .and_then(|day| day.parse::<u8>().ok());
+
+            let Some(day) = day else {
+                continue;
+            };
+
+            let source = fs::read_to_string(&path).expect("failed to read solution");
+
+            if features(&source)
+                .iter()
+                .all(|feature| FEATURES.contains(&feature.as_str()))
+            {
+                solutions.push((year, day, path));
+            }
+        }
+    }
+
+    solutions.sort_unstable();
+
+    let mut source = String::new();
+
+    // the main functions go unused here, and lints are already reported for the solution binaries
+    for (year, day, path) in &solutions {
+        writeln!(
+            source,
+            "#[path = {:?}]\n#[allow(dead_code, unused_attributes, clippy::all)]\nmod y{year}_day{day:02};",
+            path.display().to_string()
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        source,
+        "\n/// Solutions in the workspace, ordered by year and day."
+    )
+    .unwrap();
+    writeln!(source, "pub const SOLUTIONS: &[aoc::Registration] = &[").unwrap();
+
+    for (year, day, _) in &solutions {
+        writeln!(source, "    y{year}_day{day:02}::SOLUTION,").unwrap();
+    }
+
+    writeln!(source, "];").unwrap();
+
+    let out = PathBuf::from(env::var("OUT_DIR").expect("missing out dir"));
+    fs::write(out.join("solutions.rs"), source).expect("failed to write solutions");
+}
@@ -0,0 +1,16 @@
+// features only apply at the crate root, so any used by the solutions are enabled here and
+// listed in `build.rs`
+#![feature(portable_simd)]
+
+//! Solutions in the workspace compiled into one crate, so they can be run in process instead of
+//! building and starting a binary for each day. Solutions that enable crate features other than
+//! those enabled here are left out and still need their own binary.
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+/// Finds the registered solution of a puzzle.
+pub fn solution(year: u16, day: u8) -> Option<&'static aoc::Registration> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.puzzle().is_ok_and(|puzzle| puzzle == (year, day)))
+}
@@ -0,0 +1,53 @@
+use aoc::{__runner, Anyhow, Colorize};
+use aoc_solutions::SOLUTIONS;
+
+/// Runs every registered solution, or those of the year and day given with `--year` and `--day`,
+/// in a single process. Takes the same options as the solution binaries, and with `--json` prints a
+/// report of each day on its own line.
+fn main() -> Anyhow<()> {
+    let options = __runner::Options::from_args()?;
+    let parts = __runner::selected_parts();
+
+    let year = __runner::arg_value("--year")
+        .map(|year| year.parse::<u16>())
+        .transpose()?;
+
+    let day = __runner::arg_value("--day")
+        .map(|day| day.parse::<u8>())
+        .transpose()?;
+
+    for solution in SOLUTIONS {
+        let (solution_year, solution_day) = solution.puzzle()?;
+
+        if year.is_some_and(|year| year != solution_year)
+            || day.is_some_and(|day| day != solution_day)
+        {
+            continue;
+        }
+
+        if !options.json {
+            println!("{}", format!("{solution_year} day {solution_day}").bold());
+        }
+
+        let (report, error) = match solution.run(&options, &parts) {
+            Ok(report) => (Some(report), None),
+            Err(e) => {
+                eprintln!("{solution_year} day {solution_day} failed: {e}");
+                (None, Some(e.to_string()))
+            }
+        };
+
+        if options.json {
+            let report = __runner::DayReport {
+                year: solution_year,
+                day: solution_day,
+                report,
+                error,
+            };
+
+            println!("{}", report.to_json()?);
+        }
+    }
+
+    Ok(())
+}
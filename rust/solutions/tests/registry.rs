@@ -0,0 +1,28 @@
+use aoc_solutions::{SOLUTIONS, solution};
+
+#[test]
+fn registry() {
+    let puzzles = SOLUTIONS
+        .iter()
+        .map(|solution| solution.puzzle().unwrap())
+        .collect::<Vec<_>>();
+
+    assert!(!puzzles.is_empty());
+    assert!(
+        puzzles.is_sorted_by(|a, b| a < b),
+        "solutions are ordered by year and day without duplicates"
+    );
+
+    for (year, day) in puzzles {
+        let solution = solution(year, day).unwrap();
+        assert_eq!(solution.puzzle().unwrap(), (year, day));
+        assert!(!solution.parts.is_empty());
+        assert!(
+            solution
+                .path
+                .ends_with(&format!("{year}/src/bin/day{day:02}.rs"))
+        );
+    }
+
+    assert!(solution(2024, 26).is_none());
+}